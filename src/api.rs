@@ -7,7 +7,9 @@ pub (crate) enum API {
     Graph(Graph),
     Index(Index),
     Query(Query), 
-    Database(Database)
+    Database(Database),
+    Import(Import),
+    Edges(Edges)
 }
 
 pub (crate) enum Collection {
@@ -57,6 +59,14 @@ pub (crate) enum Index {
     Base
 }
 
+pub (crate) enum Import {
+    Base
+}
+
+pub (crate) enum Edges {
+    Base
+}
+
 impl From<API> for String {
     fn from(item: API) -> Self {
         String::from(match item {
@@ -108,6 +118,16 @@ impl From<API> for String {
                     Query::Slow => "slow",
                 }
             },
+            API::Import(route) => {
+                match route {
+                    Import::Base => "_api/import"
+                }
+            },
+            API::Edges(route) => {
+                match route {
+                    Edges::Base => "_api/edges"
+                }
+            },
             API::Graph(route) => {
                 match route {
                     Graph::Base => "_api/gharial",
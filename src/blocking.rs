@@ -0,0 +1,607 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    Result, Config, Arango, ArangoKeys, Doc, InsertOptions, WriteResult, WriteOptions, Patch, Tracked, Conditional, CacheOptions,
+    Edge, EdgeOptions, EdgesResponse, Direction, AsKey, AsId, DocumentId, DocumentRevision, DynWriteResult, Validator, Metadata, Timestamps, Hooks,
+    FieldRef, Filter, Sort, Page, Provider, WithParent,
+    Db, NewDatabase, NewCollection, Properties, PropertiesUpdate,
+    Information, Checksum, Count, Summary, Revision, IdResponse,
+    Idx, IndexResponse, NewIdx,
+    BoundCursorRequest, CursorRequest, CursorResponse, CacheProperties, Entry,
+    BoundExplain, Explain, ExplainResponse, ParseQuery, ParseResponse, RunningQuery
+};
+
+/*
+    Synchronous facade over the async API.
+    Every wrapper owns (or shares) a single threaded tokio runtime
+    and blocks on the matching async call.
+    Do not use these from within an async context, tokio will panic.
+*/
+
+fn runtime() -> Arc<Runtime> {
+    let runtime: Runtime = Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build blocking runtime");
+    Arc::new(runtime)
+}
+
+pub struct Database {
+    inner: crate::Database,
+    runtime: Arc<Runtime>
+}
+
+impl Arango for Database {
+    fn new(config: &Config) -> Self {
+        Self {
+            inner: Arango::new(config),
+            runtime: runtime()
+        }
+    }
+}
+
+impl Database {
+    /// List databases
+    pub fn list(&self) -> Result<Vec<String>> {
+        self.runtime.block_on(self.inner.list())
+    }
+
+    /// Create a database
+    /// Must be in _system database.
+    pub fn create(&self, db: &NewDatabase) -> Result<bool> {
+        self.runtime.block_on(self.inner.create(db))
+    }
+
+    /// Information of the database
+    pub fn current(&self) -> Result<Db> {
+        self.runtime.block_on(self.inner.current())
+    }
+
+    /// List of accessible databases for user
+    pub fn user(&self) -> Result<Vec<String>> {
+        self.runtime.block_on(self.inner.user())
+    }
+
+    /// Drop a database
+    pub fn drop(&self, database: &str) -> Result<bool> {
+        self.runtime.block_on(self.inner.drop(database))
+    }
+
+    /// Create a new collection
+    pub fn new_collection(&self, new_collection: &NewCollection) -> Result<Properties> {
+        self.runtime.block_on(self.inner.new_collection(new_collection))
+    }
+
+    /// The returned collection shares the runtime of this database.
+    pub fn collection(&self, name: &str) -> Collection {
+        Collection {
+            inner: self.inner.collection(name),
+            runtime: self.runtime.clone()
+        }
+    }
+}
+
+pub struct Collection {
+    inner: crate::Collection,
+    runtime: Arc<Runtime>
+}
+
+impl Collection {
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Read all collections
+    pub fn read(&self) -> Result<Vec<Information>> {
+        self.runtime.block_on(self.inner.read())
+    }
+
+    /// Drop collection
+    pub fn drop(&self) -> Result<IdResponse> {
+        self.runtime.block_on(self.inner.drop())
+    }
+
+    /// Get information about a collection
+    pub fn information(&self) -> Result<Information> {
+        self.runtime.block_on(self.inner.information())
+    }
+
+    /// Get Checksum
+    pub fn checksum(&self) -> Result<Checksum> {
+        self.runtime.block_on(self.inner.checksum())
+    }
+
+    /// Compact
+    pub fn compact(&self) -> Result<Information> {
+        self.runtime.block_on(self.inner.compact())
+    }
+
+    /// Count
+    pub fn count(&self) -> Result<Count> {
+        self.runtime.block_on(self.inner.count())
+    }
+
+    /// Figures
+    pub fn figures(&self) -> Result<Summary> {
+        self.runtime.block_on(self.inner.figures())
+    }
+
+    /// Get the properties of the collection
+    pub fn properties(&self) -> Result<Properties> {
+        self.runtime.block_on(self.inner.properties())
+    }
+
+    /// Update the properties of the collection
+    pub fn update_properties(&self, properties: &PropertiesUpdate) -> Result<Properties> {
+        self.runtime.block_on(self.inner.update_properties(properties))
+    }
+
+    /// Load Indexes into Memory
+    pub fn load_indexes(&self) -> Result<bool> {
+        self.runtime.block_on(self.inner.load_indexes())
+    }
+
+    /// Recalculate Count
+    pub fn recalculate_count(&self) -> Result<bool> {
+        self.runtime.block_on(self.inner.recalculate_count())
+    }
+
+    /// Rename
+    pub fn rename(&mut self, new_collection_name: &str) -> Result<Information> {
+        self.runtime.block_on(self.inner.rename(new_collection_name))
+    }
+
+    /// Revision
+    pub fn revision(&self) -> Result<Revision> {
+        self.runtime.block_on(self.inner.revision())
+    }
+
+    /// Truncate
+    pub fn truncate(&self) -> Result<Information> {
+        self.runtime.block_on(self.inner.truncate())
+    }
+}
+
+pub struct Document<M = Timestamps> {
+    inner: crate::Document<M>,
+    runtime: Arc<Runtime>
+}
+
+impl<M> Arango for Document<M> {
+    fn new(config: &Config) -> Self {
+        Self {
+            inner: Arango::new(config),
+            runtime: runtime()
+        }
+    }
+}
+
+impl<M: Metadata> Document<M> {
+    /// The same handle for documents with the metadata policy N
+    pub fn with_metadata<N: Metadata>(self) -> Document<N> {
+        Document {
+            inner: self.inner.with_metadata(),
+            runtime: self.runtime
+        }
+    }
+
+    /// Serve read and read_many from a bounded LRU cache
+    pub fn with_cache(self, options: CacheOptions) -> Self {
+        Self {
+            inner: self.inner.with_cache(options),
+            runtime: self.runtime
+        }
+    }
+
+    /// Run hooks around the writes of records R
+    pub fn with_hooks<R: Serialize + DeserializeOwned + Clone + Sync + Send + 'static, H: Hooks<R> + 'static>(self, hooks: H) -> Self {
+        Self {
+            inner: self.inner.with_hooks(hooks),
+            runtime: self.runtime
+        }
+    }
+
+    /// Insert document into the collection
+    pub fn insert<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, model: &R, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.insert(model, options))
+    }
+
+    /// Insert document into the collection
+    pub fn insert_with_options<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, model: &R, options: &InsertOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.insert_with_options(model, options))
+    }
+
+    /// Insert a document under key, or merge the record into the existing one.
+    pub fn upsert<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, key: &(impl AsKey + ?Sized), model: &R, options: &InsertOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.upsert(key, model, options))
+    }
+
+    /// Insert an edge between two vertices
+    pub fn insert_edge<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, from: &(impl AsId + ?Sized), to: &(impl AsId + ?Sized), data: R, options: &EdgeOptions) -> Result<Doc<Edge<R>, M>> {
+        self.runtime.block_on(self.inner.insert_edge(from, to, data, options))
+    }
+
+    /// Edges of the collection of R at a vertex
+    pub fn edges<R: Serialize + DeserializeOwned + Sync + Send>(&self, vertex: &DocumentId, direction: Direction) -> Result<EdgesResponse<R, M>> {
+        self.runtime.block_on(self.inner.edges(vertex, direction))
+    }
+
+    /// Insert many documents into the collection
+    pub fn insert_many<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, models: &[R]) -> Result<Vec<ArangoKeys>> {
+        self.runtime.block_on(self.inner.insert_many(models))
+    }
+
+    /// Insert a document after validating it
+    pub fn insert_validated<R: Validator + DeserializeOwned + Sync + Send + 'static>(&self, model: &R, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.insert_validated(model, options))
+    }
+
+    /// Insert many documents, nothing is inserted unless every record is valid
+    pub fn insert_many_validated<R: Validator + Serialize + DeserializeOwned + Sync + Send + 'static>(&self, models: &[R]) -> Result<Vec<ArangoKeys>> {
+        self.runtime.block_on(self.inner.insert_many_validated(models))
+    }
+
+    /// Update a document after validating its record
+    pub fn update_validated<R: Validator + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.update_validated(doc, options))
+    }
+
+    /// Replace a document after validating its record
+    pub fn replace_validated<R: Validator + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.replace_validated(doc, options))
+    }
+
+    /// Read a document by key
+    pub fn read<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized)) -> Result<Doc<R, M>> {
+        self.runtime.block_on(self.inner.read(key))
+    }
+
+    /// Read a document of any collection by `_id`
+    pub fn read_by_id<R: Serialize + DeserializeOwned + Sync + Send>(&self, id: &DocumentId) -> Result<Doc<R, M>> {
+        self.runtime.block_on(self.inner.read_by_id(id))
+    }
+
+    /// Resolve references to documents of several collections
+    pub fn read_ids(&self, ids: &[DocumentId]) -> Result<HashMap<DocumentId, Value>> {
+        self.runtime.block_on(self.inner.read_ids(ids))
+    }
+
+    /// Read multiple documents by key
+    pub fn read_many<S: Serialize + Sized + Sync + Send, R: Serialize + DeserializeOwned + Sync + Send>(&self, keys: &[S]) -> Result<Vec<Doc<R, M>>> {
+        self.runtime.block_on(self.inner.read_many(keys))
+    }
+
+    /// Read a document header by key
+    pub fn read_header<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized)) -> Result<DocumentRevision> {
+        self.runtime.block_on(self.inner.read_header::<R>(key))
+    }
+
+    /// Read a document by key unless its revision is still `rev`
+    pub fn read_if_changed<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized), rev: &DocumentRevision) -> Result<Conditional<R, M>> {
+        self.runtime.block_on(self.inner.read_if_changed(key, rev))
+    }
+
+    /// Delete a document by key
+    pub fn delete<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, key: &(impl AsKey + ?Sized), options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.delete(key, options))
+    }
+
+    /// Update a document
+    pub fn update<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.update(doc, options))
+    }
+
+    /// Update the attributes of a patch
+    pub fn patch<R: Serialize + DeserializeOwned + Sync + Send + 'static, P: Patch<R>>(&self, key: &(impl AsKey + ?Sized), patch: &P, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.patch(key, patch, options))
+    }
+
+    /// Read a document by key and track changes to its record
+    pub fn read_tracked<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized)) -> Result<Tracked<R, M>> {
+        self.runtime.block_on(self.inner.read_tracked(key))
+    }
+
+    /// Patch the changed attributes of a tracked document
+    pub fn save<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, tracked: &mut Tracked<R, M>) -> Result<Option<WriteResult<R, M>>> {
+        self.runtime.block_on(self.inner.save(tracked))
+    }
+
+    /// Replace a document
+    pub fn replace<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.replace(doc, options))
+    }
+
+    /// Destroy a document
+    pub fn destroy<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>) -> Result<WriteResult<R, M>> {
+        self.runtime.block_on(self.inner.destroy(doc))
+    }
+}
+
+pub struct DynDocument {
+    inner: crate::DynDocument,
+    runtime: Arc<Runtime>
+}
+
+impl Arango for DynDocument {
+    fn new(config: &Config) -> Self {
+        Self {
+            inner: Arango::new(config),
+            runtime: runtime()
+        }
+    }
+}
+
+impl DynDocument {
+    /// Insert a document into collection
+    pub fn insert<D: Serialize + Sync + Send>(&self, collection: &str, document: &D, options: &WriteOptions) -> Result<DynWriteResult> {
+        self.runtime.block_on(self.inner.insert(collection, document, options))
+    }
+
+    /// Read a document by key
+    pub fn read(&self, collection: &str, key: &(impl AsKey + ?Sized)) -> Result<Value> {
+        self.runtime.block_on(self.inner.read(collection, key))
+    }
+
+    /// Read multiple documents by key
+    pub fn read_many<S: Serialize + Sync + Send>(&self, collection: &str, keys: &[S]) -> Result<Vec<Value>> {
+        self.runtime.block_on(self.inner.read_many(collection, keys))
+    }
+
+    /// Merge the attributes of patch into a document
+    pub fn update<D: Serialize + Sync + Send>(&self, collection: &str, key: &(impl AsKey + ?Sized), patch: &D, options: &WriteOptions) -> Result<DynWriteResult> {
+        self.runtime.block_on(self.inner.update(collection, key, patch, options))
+    }
+
+    /// Replace a document
+    pub fn replace<D: Serialize + Sync + Send>(&self, collection: &str, key: &(impl AsKey + ?Sized), document: &D, options: &WriteOptions) -> Result<DynWriteResult> {
+        self.runtime.block_on(self.inner.replace(collection, key, document, options))
+    }
+
+    /// Delete a document
+    pub fn delete(&self, collection: &str, key: &(impl AsKey + ?Sized), options: &WriteOptions) -> Result<DynWriteResult> {
+        self.runtime.block_on(self.inner.delete(collection, key, options))
+    }
+}
+
+pub struct Repository<R, M = Timestamps> {
+    inner: crate::Repository<R, M>,
+    runtime: Arc<Runtime>
+}
+
+impl<R, M> Arango for Repository<R, M> {
+    fn new(config: &Config) -> Self {
+        Self {
+            inner: Arango::new(config),
+            runtime: runtime()
+        }
+    }
+}
+
+impl<R, M> Repository<R, M>
+where
+    R: Serialize + DeserializeOwned + Sync + Send + 'static,
+    M: Metadata {
+    /// Serve get and get_many from a bounded LRU cache
+    pub fn with_cache(self, options: CacheOptions) -> Self {
+        Self {
+            inner: self.inner.with_cache(options),
+            runtime: self.runtime
+        }
+    }
+
+    /// Run hooks around the writes of the repository
+    pub fn with_hooks<H: Hooks<R> + 'static>(self, hooks: H) -> Self
+    where
+        R: Clone {
+        Self {
+            inner: self.inner.with_hooks(hooks),
+            runtime: self.runtime
+        }
+    }
+
+    /// Read a document by key
+    pub fn get(&self, key: &(impl AsKey + ?Sized)) -> Result<Doc<R, M>> {
+        self.runtime.block_on(self.inner.get(key))
+    }
+
+    /// Read multiple documents by key, fails if one is missing
+    pub fn get_many<S: Serialize + Sync + Send>(&self, keys: &[S]) -> Result<Vec<Doc<R, M>>> {
+        self.runtime.block_on(self.inner.get_many(keys))
+    }
+
+    /// Documents whose field equals value
+    pub fn find_by(&self, field: impl Into<FieldRef>, value: impl Into<Value>) -> Result<Vec<Doc<R, M>>> {
+        self.runtime.block_on(self.inner.find_by(field, value))
+    }
+
+    /// Documents matching filter
+    pub fn find(&self, filter: &Filter) -> Result<Vec<Doc<R, M>>> {
+        self.runtime.block_on(self.inner.find(filter))
+    }
+
+    /// Whether a document with key exists
+    pub fn exists(&self, key: &(impl AsKey + ?Sized)) -> Result<bool> {
+        self.runtime.block_on(self.inner.exists(key))
+    }
+
+    /// Number of documents in the collection
+    pub fn count(&self) -> Result<u64> {
+        self.runtime.block_on(self.inner.count())
+    }
+
+    /// Number of documents matching filter
+    pub fn count_where(&self, filter: &Filter) -> Result<u64> {
+        self.runtime.block_on(self.inner.count_where(filter))
+    }
+
+    /// Insert a record, the stored document is returned
+    pub fn insert(&self, record: &R) -> Result<Doc<R, M>> {
+        self.runtime.block_on(self.inner.insert(record))
+    }
+
+    /// Write the record of a document, the stored document is returned
+    pub fn save(&self, doc: &Doc<R, M>) -> Result<Doc<R, M>> {
+        self.runtime.block_on(self.inner.save(doc))
+    }
+
+    /// Delete a document by key
+    pub fn delete(&self, key: &(impl AsKey + ?Sized)) -> Result<()> {
+        self.runtime.block_on(self.inner.delete(key))
+    }
+
+    /// limit documents from offset, in the order of sort
+    pub fn page(&self, offset: u64, limit: u64, sort: &[Sort]) -> Result<Page<R, M>> {
+        self.runtime.block_on(self.inner.page(offset, limit, sort))
+    }
+
+    /// Insert a child of parent, it is returned with its parent
+    pub fn add_child<P: Provider<R> + Serialize + DeserializeOwned + Sync + Send>(&self, parent: &(impl AsKey + ?Sized), child: &R) -> Result<WithParent<R, P, M>> {
+        self.runtime.block_on(self.inner.add_child(parent, child))
+    }
+
+    /// Children of parent
+    pub fn children<P: Provider<R>>(&self, parent: &(impl AsKey + ?Sized)) -> Result<Vec<Doc<R, M>>> {
+        self.runtime.block_on(self.inner.children::<P>(parent))
+    }
+
+    /// Parents of a child, at most one for Relation::Foreign
+    pub fn parents<P: Provider<R> + DeserializeOwned + Sync + Send>(&self, child: &(impl AsKey + ?Sized)) -> Result<Vec<Doc<P, M>>> {
+        self.runtime.block_on(self.inner.parents(child))
+    }
+
+    /// Parent of a child, the first one of a many-to-many relation
+    pub fn parent<P: Provider<R> + DeserializeOwned + Sync + Send>(&self, child: &(impl AsKey + ?Sized)) -> Result<Option<Doc<P, M>>> {
+        self.runtime.block_on(self.inner.parent(child))
+    }
+
+    /// Children matching filter, loaded with their parent in the same query
+    pub fn with_parent<P: Provider<R> + DeserializeOwned + Sync + Send>(&self, filter: &Filter) -> Result<Vec<WithParent<R, P, M>>> {
+        self.runtime.block_on(self.inner.with_parent(filter))
+    }
+}
+
+pub struct Query {
+    inner: crate::Query,
+    runtime: Arc<Runtime>
+}
+
+impl Arango for Query {
+    fn new(config: &Config) -> Self {
+        Self {
+            inner: Arango::new(config),
+            runtime: runtime()
+        }
+    }
+}
+
+impl Query {
+    /// Create Bound Cursor
+    pub fn bound_cursor<B: Serialize + Send + Sync, R: DeserializeOwned + Send + Sync>(&self, request: &BoundCursorRequest<B>) -> Result<CursorResponse<R>> {
+        self.runtime.block_on(self.inner.bound_cursor(request))
+    }
+
+    /// Create Cursor
+    pub fn cursor<R: DeserializeOwned + Send + Sync>(&self, request: &CursorRequest) -> Result<CursorResponse<R>> {
+        self.runtime.block_on(self.inner.cursor(request))
+    }
+
+    /// Delete Cursor
+    pub fn delete_cursor(&self, cursor_id: String) -> Result<IdResponse> {
+        self.runtime.block_on(self.inner.delete_cursor(cursor_id))
+    }
+
+    /// Clears results in the AQL query results cache
+    pub fn clear_cache(&self) -> Result<()> {
+        self.runtime.block_on(self.inner.clear_cache())
+    }
+
+    /// Returns the currently cached query results
+    pub fn cache_entries(&self) -> Result<Vec<Entry>> {
+        self.runtime.block_on(self.inner.cache_entries())
+    }
+
+    /// Returns the global properties for the AQL query results cache
+    pub fn cache_properties(&self) -> Result<CacheProperties> {
+        self.runtime.block_on(self.inner.cache_properties())
+    }
+
+    /// Globally adjusts the AQL query results cache properties
+    pub fn set_cache_properties(&self, properties: &CacheProperties) -> Result<CacheProperties> {
+        self.runtime.block_on(self.inner.set_cache_properties(properties))
+    }
+
+    /// Explain an AQL query
+    pub fn bound_explain<B: Serialize + Send + Sync, R: DeserializeOwned + Send + Sync>(&self, query: &BoundExplain<B>) -> Result<ExplainResponse> {
+        self.runtime.block_on(self.inner.bound_explain::<B, R>(query))
+    }
+
+    /// Explain an AQL query
+    pub fn explain(&self, query: &Explain) -> Result<ExplainResponse> {
+        self.runtime.block_on(self.inner.explain(query))
+    }
+
+    /// Parse an AQL query
+    pub fn parse(&self, query: &ParseQuery) -> Result<ParseResponse> {
+        self.runtime.block_on(self.inner.parse(query))
+    }
+
+    /// Returns the currently running AQL queries
+    pub fn running(&self) -> Result<Vec<RunningQuery>> {
+        self.runtime.block_on(self.inner.running())
+    }
+
+    /// Clears the list of slow AQL queries
+    pub fn clear_slow(&self) -> Result<()> {
+        self.runtime.block_on(self.inner.clear_slow())
+    }
+
+    /// Returns the list of slow AQL queries
+    pub fn slow(&self) -> Result<Vec<RunningQuery>> {
+        self.runtime.block_on(self.inner.slow())
+    }
+
+    /// Kills a running AQL query
+    pub fn kill(&self, query_id: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.kill(query_id))
+    }
+}
+
+pub struct Index {
+    inner: crate::Index,
+    runtime: Arc<Runtime>
+}
+
+impl Arango for Index {
+    fn new(config: &Config) -> Self {
+        Self {
+            inner: Arango::new(config),
+            runtime: runtime()
+        }
+    }
+}
+
+impl Index {
+    /// Read all indexes of a collection
+    pub fn read(&self, collection_name: &str) -> Result<Vec<Idx>> {
+        self.runtime.block_on(self.inner.read(collection_name))
+    }
+
+    /// Get index for collection
+    pub fn get(&self, id: &str) -> Result<IndexResponse> {
+        self.runtime.block_on(self.inner.get(id))
+    }
+
+    /// Create an index
+    pub fn create(&self, new_index: &NewIdx) -> Result<IndexResponse> {
+        self.runtime.block_on(self.inner.create(new_index))
+    }
+
+    /// Delete an index
+    pub fn delete(&self, id: &str) -> Result<IdResponse> {
+        self.runtime.block_on(self.inner.delete(id))
+    }
+}
@@ -0,0 +1,172 @@
+use std::{collections::{BTreeMap, HashMap}, sync::Mutex, time::{Duration, Instant}};
+
+use serde_json::Value;
+
+/*
+    Read-through document cache of a Document handle.
+    Documents are kept as JSON so a single cache serves every record type.
+    Entries older than the ttl are revalidated by revision before they are served again.
+*/
+
+#[derive(Clone, Copy, Debug)]
+pub struct CacheOptions {
+    /// Maximum number of cached documents, the least recently used are evicted first
+    pub capacity: usize,
+    /// Age after which an entry is revalidated with the server
+    pub ttl: Duration
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            ttl: Duration::from_secs(60)
+        }
+    }
+}
+
+/// Cached document, `fresh` when younger than the ttl
+pub(crate) struct Cached {
+    pub document: Value,
+    pub rev: String,
+    pub fresh: bool
+}
+
+struct Entry {
+    document: Value,
+    rev: String,
+    validated: Instant,
+    used: u64
+}
+
+#[derive(Default)]
+struct Entries {
+    entries: HashMap<(String, String), Entry>,
+    /// use counter -> key, the first entry is the least recently used
+    order: BTreeMap<u64, (String, String)>,
+    counter: u64
+}
+
+pub(crate) struct DocumentCache {
+    options: CacheOptions,
+    entries: Mutex<Entries>
+}
+
+impl DocumentCache {
+    pub fn new(options: CacheOptions) -> Self {
+        Self {
+            options,
+            entries: Mutex::new(Entries::default())
+        }
+    }
+
+    pub fn get(&self, collection: &str, key: &str) -> Option<Cached> {
+        let mut guard = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entries: &mut Entries = &mut guard;
+        let id: (String, String) = (collection.to_owned(), key.to_owned());
+
+        entries.counter += 1;
+        let counter: u64 = entries.counter;
+        let entry: &mut Entry = entries.entries.get_mut(&id)?;
+        entries.order.remove(&entry.used);
+        entry.used = counter;
+        entries.order.insert(counter, id);
+
+        Some(Cached {
+            document: entry.document.clone(),
+            rev: entry.rev.clone(),
+            fresh: entry.validated.elapsed() < self.options.ttl
+        })
+    }
+
+    /// The cached revision is still current
+    pub fn revalidated(&self, collection: &str, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.entries.get_mut(&(collection.to_owned(), key.to_owned())) {
+            entry.validated = Instant::now();
+        }
+    }
+
+    pub fn put(&self, collection: &str, key: &str, rev: &str, document: Value) {
+        if self.options.capacity == 0 {
+            return;
+        }
+        let mut guard = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entries: &mut Entries = &mut guard;
+        let id: (String, String) = (collection.to_owned(), key.to_owned());
+
+        entries.counter += 1;
+        let counter: u64 = entries.counter;
+        if let Some(previous) = entries.entries.remove(&id) {
+            entries.order.remove(&previous.used);
+        }
+        while entries.entries.len() >= self.options.capacity {
+            match entries.order.pop_first() {
+                Some((_, evicted)) => {
+                    entries.entries.remove(&evicted);
+                },
+                None => break,
+            }
+        }
+        entries.order.insert(counter, id.clone());
+        entries.entries.insert(id, Entry {
+            document,
+            rev: rev.to_owned(),
+            validated: Instant::now(),
+            used: counter
+        });
+    }
+
+    pub fn invalidate(&self, collection: &str, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.entries.remove(&(collection.to_owned(), key.to_owned())) {
+            entries.order.remove(&entry.used);
+        }
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).entries.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{CacheOptions, DocumentCache};
+
+    #[test]
+    fn test_lru_eviction() {
+        let cache: DocumentCache = DocumentCache::new(CacheOptions { capacity: 2, ttl: Duration::from_secs(60) });
+
+        cache.put("sample", "1", "a", json!({ "_key": "1" }));
+        cache.put("sample", "2", "b", json!({ "_key": "2" }));
+
+        // 1 becomes the most recently used, 2 is evicted
+        assert!(cache.get("sample", "1").is_some());
+        cache.put("sample", "3", "c", json!({ "_key": "3" }));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("sample", "2").is_none());
+        assert_eq!(cache.get("sample", "1").unwrap().rev, "a");
+        assert!(cache.get("sample", "3").unwrap().fresh);
+
+        cache.invalidate("sample", "1");
+        assert!(cache.get("sample", "1").is_none());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_ttl() {
+        let cache: DocumentCache = DocumentCache::new(CacheOptions { capacity: 2, ttl: Duration::ZERO });
+
+        cache.put("sample", "1", "a", json!({ "_key": "1" }));
+        assert!(!cache.get("sample", "1").unwrap().fresh);
+
+        // same key in another collection
+        assert!(cache.get("other", "1").is_none());
+    }
+}
@@ -1,14 +1,16 @@
-use std::{fmt, error, time::Duration};
+use std::{fmt, error, io::{self, Read, Write}, time::Duration};
 
 use base64::{Engine as _, engine::general_purpose};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use hyper::{
     client::HttpConnector, Response, Body, body::Buf, 
     http::{self, request::Builder, uri::InvalidUri}, 
-    Request, Method, HeaderMap
+    Request, Method, HeaderMap,
+    header::{HeaderValue, CONTENT_TYPE, ACCEPT, CONTENT_ENCODING, ACCEPT_ENCODING}
 };
+use flate2::{write::{GzEncoder, ZlibEncoder}, read::{GzDecoder, ZlibDecoder}};
 use log::info;
-use crate::{api::{ApiError, self, API}, DocumentQueryParams};
+use crate::{api::{ApiError, self, API}, DocumentQueryParams, vpack, request, ArrayStream};
 
 
 
@@ -20,8 +22,14 @@ pub enum ClientError {
     Uri(InvalidUri),
     Params(serde_url_params::Error),
     JsonError(serde_json::Error),
+    VelocyPack(vpack::Error),
+    Io(io::Error),
     Insertion(String),
-    Validation(String)
+    Validation(String),
+    /// A query expected to return a result returned nothing
+    NotFound(String),
+    /// Failed validations of a bulk write with the index of their record
+    ValidationFailures(Vec<(usize, ClientError)>)
 }
 
 pub type Result<T> = std::result::Result<T, ClientError>;
@@ -41,6 +49,8 @@ impl error::Error for ClientError {
             Self::Api(ref e) => Some(e),
             Self::Params(ref e) => Some(e),
             Self::JsonError(ref e) => Some(e),
+            Self::VelocyPack(ref e) => Some(e),
+            Self::Io(ref e) => Some(e),
             // Self::Insertion(ref e) => Some(e),
             // Self::Validation(ref e) => Some(e),
             _ => None,
@@ -56,6 +66,10 @@ impl ClientError {
     pub fn insertion_error(message: String) -> Self {
         Self::Insertion(message)
     }
+
+    pub fn not_found_error(message: String) -> Self {
+        Self::NotFound(message)
+    }
 }
 
 impl From<hyper::Error> for ClientError{
@@ -70,6 +84,18 @@ impl From<serde_json::Error> for ClientError{
     }
 }
 
+impl From<vpack::Error> for ClientError{
+    fn from(value: vpack::Error) -> ClientError {
+        return ClientError::VelocyPack(value);
+    }
+}
+
+impl From<io::Error> for ClientError{
+    fn from(value: io::Error) -> ClientError {
+        return ClientError::Io(value);
+    }
+}
+
 impl From<http::uri::InvalidUri> for ClientError{
     fn from(value: http::uri::InvalidUri) -> Self {
         return ClientError::Uri(value);
@@ -164,12 +190,15 @@ pub (crate) trait DocumentRouter {
             encoded_params
         ))
     }
+    fn read_many(collection_name: &str) -> String {
+        return format!("{}?onlyget=true", Self::base(collection_name));
+    }
     fn key(collection_name: &str, key: &str) -> String {
-        return format!("{}/{}", Self::base(collection_name), key);
+        return format!("{}/{}", Self::base(collection_name), request::encode_segment(key));
     }
     fn key_with_params(collection_name: &str, key: &str, params: DocumentQueryParams) -> Result<String> {
         let encoded_params: String = serde_url_params::to_string(&params)?;
-        Ok(format!("{}/{}?{}", Self::base(collection_name), key, encoded_params))
+        Ok(format!("{}?{}", Self::key(collection_name, key), encoded_params))
     } 
 }
 
@@ -223,6 +252,28 @@ pub (crate) trait IndexRouter {
     }
 }
 
+pub (crate) trait ImportRouter {
+    fn base_with_params<P: Serialize>(collection_name: &str, params: P) -> Result<String> {
+        let encoded_params: String = serde_url_params::to_string(&params)?;
+        Ok(format!("{}?collection={}&{}", 
+            String::from(api::API::Import(api::Import::Base)), 
+            collection_name,
+            encoded_params
+        ))
+    }
+}
+
+pub (crate) trait EdgesRouter {
+    fn base_with_params<P: Serialize>(collection_name: &str, params: P) -> Result<String> {
+        let encoded_params: String = serde_url_params::to_string(&params)?;
+        Ok(format!("{}/{}?{}", 
+            String::from(api::API::Edges(api::Edges::Base)), 
+            collection_name,
+            encoded_params
+        ))
+    }
+}
+
 #[derive(Clone)]
 pub (crate) struct Router {}
 
@@ -231,12 +282,119 @@ impl DatabaseRouter for Router{}
 impl DocumentRouter for Router{}
 impl QueryRouter for Router{}
 impl IndexRouter for Router{}
+impl ImportRouter for Router{}
+impl EdgesRouter for Router{}
+
+/// Encoding used for request and response bodies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ContentType {
+    #[default]
+    Json,
+    VelocyPack
+}
+
+impl ContentType {
+    pub fn mime(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::VelocyPack => vpack::CONTENT_TYPE,
+        }
+    }
+
+    pub(crate) fn encode<K: Serialize>(&self, data: &K) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(data)?),
+            Self::VelocyPack => Ok(vpack::to_vec(data)?),
+        }
+    }
+
+    /// Resolve the encoding of a response from its Content-Type header.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        match headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok()) {
+            Some(value) if value.starts_with(vpack::CONTENT_TYPE) => Self::VelocyPack,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// Content codings supported for request and response bodies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Gzip,
+    /// zlib wrapped deflate, as HTTP defines it.
+    Deflate
+}
+
+impl Encoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+
+    pub fn compress(&self, data: &[u8], level: u32) -> io::Result<Vec<u8>> {
+        let level: flate2::Compression = flate2::Compression::new(level);
+        match self {
+            Self::Gzip => {
+                let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), level);
+                encoder.write_all(data)?;
+                encoder.finish()
+            },
+            Self::Deflate => {
+                let mut encoder: ZlibEncoder<Vec<u8>> = ZlibEncoder::new(Vec::new(), level);
+                encoder.write_all(data)?;
+                encoder.finish()
+            },
+        }
+    }
+
+    pub fn decoder<'a, R: Read + 'a>(&self, reader: R) -> Box<dyn Read + 'a> {
+        match self {
+            Self::Gzip => Box::new(GzDecoder::new(reader)),
+            Self::Deflate => Box::new(ZlibDecoder::new(reader)),
+        }
+    }
+
+    /// Resolve the coding of a response from its Content-Encoding header.
+    /// Returns None for identity or unknown codings.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        match headers.get(CONTENT_ENCODING).and_then(|value| value.to_str().ok()) {
+            Some("gzip") | Some("x-gzip") => Some(Self::Gzip),
+            Some("deflate") => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+}
+
+/// Opt-in body compression.
+/// Request bodies of at least `threshold` bytes are compressed with `encoding`,
+/// responses are requested compressed and transparently decompressed.
+#[derive(Clone, Copy, Debug)]
+pub struct Compression {
+    pub encoding: Encoding,
+    pub threshold: usize,
+    /// 0 (fastest) to 9 (smallest)
+    pub level: u32
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self {
+            encoding: Encoding::Gzip,
+            threshold: 64 * 1024,
+            level: 6
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Client {
     host: String,
     database: String,
     secret: String,
+    content_type: ContentType,
+    compression: Option<Compression>,
     client: hyper::Client<HttpConnector>
 }
 
@@ -259,10 +417,35 @@ impl Client {
             host: config.host.clone(),
             database: config.database.clone(),
             secret: b64,
+            content_type: ContentType::default(),
+            compression: None,
             client
         }
     }
 
+    /// Encode request bodies and request responses using the given content type.
+    /// ```ignore
+    /// let document: Document = Document::from(Client::new(&config).with_content_type(ContentType::VelocyPack));
+    /// ```
+    pub fn with_content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    pub fn content_type(&self) -> ContentType {
+        self.content_type
+    }
+
+    /// Compress large request bodies and accept compressed responses.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    pub fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
     fn to_base(&self) -> String {
         return format!("{0}/_db/{1}/", self.host, self.database);
     }
@@ -271,148 +454,204 @@ impl Client {
         headers.insert("Authorization", format!("Basic {}", self.secret).parse().unwrap());
     }
 
-    pub async fn head<T: DeserializeOwned>(&self, endpoint: String) -> Result<T> {
+    fn apply_content_type(&self, headers: &mut HeaderMap) {
+        let mime: HeaderValue = HeaderValue::from_static(self.content_type.mime());
+        headers.insert(CONTENT_TYPE, mime.clone());
+        headers.insert(ACCEPT, mime);
+    }
+
+    /// Request builder for the endpoint with authentication and content negotiation applied.
+    pub(crate) fn builder(&self, method: Method, endpoint: String) -> Builder {
         let url: String = format!("{}{}", self.to_base(), endpoint);
 
-        let mut builder: Builder = Request::head(url);
-        {   
-            match builder.headers_mut() {
-                Some(h) => self.apply_authentication(h),
-                _ => {}
+        let mut builder: Builder = Request::builder()
+            .method(method)
+            .uri(url);
+        if let Some(h) = builder.headers_mut() {
+            self.apply_authentication(h);
+            self.apply_content_type(h);
+            if self.compression.is_some() {
+                h.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate"));
+            }
+        }
+        builder
+    }
+
+    /// Encode the request body, compressing it when it crosses the configured threshold.
+    fn with_body<K: Serialize>(&self, builder: Builder, data: &K) -> Result<Request<Body>> {
+        self.with_encoded(builder, self.content_type.encode(data)?)
+    }
+
+    /// Attach an already encoded request body, compressing it when it crosses the configured threshold.
+    pub(crate) fn with_encoded(&self, mut builder: Builder, mut encoded: Vec<u8>) -> Result<Request<Body>> {
+        if let Some(compression) = self.compression.filter(|c| !encoded.is_empty() && encoded.len() >= c.threshold) {
+            encoded = compression.encoding.compress(&encoded, compression.level)?;
+            if let Some(h) = builder.headers_mut() {
+                h.insert(CONTENT_ENCODING, HeaderValue::from_static(compression.encoding.name()));
             }
         }
-        let request: Request<Body> = builder.body(Body::from(""))?;
+        Ok(builder.body(Body::from(encoded))?)
+    }
+
+    pub async fn head<T: DeserializeOwned>(&self, endpoint: String) -> Result<T> {
+        let request: Request<Body> = self.builder(Method::HEAD, endpoint).body(Body::from(""))?;
         let res: Response<Body> = self.client.request(request).await?;
         self.handler(res).await
     }
 
     pub async fn get<T: DeserializeOwned>(&self, endpoint: String) -> Result<T> {
-        let url: String = format!("{}{}", self.to_base(), endpoint);
-
-        info!("REQUEST: {:#?}", url);
+        info!("REQUEST: {:#?}", endpoint);
 
-        println!("GET {:#?}", url);
+        println!("GET {:#?}", endpoint);
 
-        let mut builder: Builder = Request::get(url);
-        {   
-            match builder.headers_mut() {
-                Some(h) => self.apply_authentication(h),
-                _ => {}
-            }
-        }
-        let request: Request<Body> = builder.body(hyper::Body::default())?;
+        let request: Request<Body> = self.builder(Method::GET, endpoint).body(hyper::Body::default())?;
         let res: Response<Body> = self.client.request(request).await?;
         self.handler(res).await
     }
 
     pub async fn post<T: DeserializeOwned, K: Serialize>(&self, endpoint: String, data: &K) -> Result<T>{
-        let url: String = format!("{}{}", self.to_base(), endpoint);
-
-        let mut builder: Builder = Request::builder()
-            .method(Method::POST)
-            .uri(url);
-        {   
-            match builder.headers_mut() {
-                Some(h) => self.apply_authentication(h),
-                _ => {}
-            }
-        }
-        let request: Request<Body> = builder.body(Body::from(serde_json::to_string(&data)?))?;
+        let request: Request<Body> = self.with_body(self.builder(Method::POST, endpoint), data)?;
         let res: Response<Body> = self.client.request(request).await?;
         self.handler(res).await
     }
 
     pub async fn patch<T: DeserializeOwned, K: Serialize>(&self, endpoint: String, data: &K) -> Result<T>{
-        let url: String = format!("{}{}", self.to_base(), endpoint);
-
-        let mut builder: Builder = Request::builder()
-            .method(Method::PATCH)
-            .uri(url);
-        {
-            match builder.headers_mut() {
-                Some(h) => self.apply_authentication(h),
-                _ => {}
-            }
-        }
-        let request: Request<Body> = builder.body(Body::from(serde_json::to_string(&data)?))?;
+        let request: Request<Body> = self.with_body(self.builder(Method::PATCH, endpoint), data)?;
         let res: Response<Body> = self.client.request(request).await?;
         self.handler(res).await
     }
 
     pub async fn put<T: DeserializeOwned, K: Serialize>(&self, endpoint: String, data: &K) -> Result<T>{
-        let url: String = format!("{}{}", self.to_base(), endpoint);
-
-        let mut builder: Builder = Request::builder()
-            .method(Method::PUT)
-            .uri(url);
-        {
-            match builder.headers_mut() {
-                Some(h) => self.apply_authentication(h),
-                _ => {}
-            }
-        }
-        let request: Request<Body> = builder.body(Body::from(serde_json::to_string(&data)?))?;
+        let request: Request<Body> = self.with_body(self.builder(Method::PUT, endpoint), data)?;
         let res: Response<Body> = self.client.request(request).await?;
         self.handler(res).await
     }
 
     pub async fn delete<T: DeserializeOwned>(&self, endpoint: String) -> Result<T> {
-        let url: String = format!("{}{}", self.to_base(), endpoint);
-
-        let mut builder: Builder = Request::builder()
-            .method(Method::DELETE)
-            .uri(url);
-        {
-            match builder.headers_mut() {
-                Some(h) => self.apply_authentication(h),
-                _ => {}
-            }
-        }
-        let request: Request<Body> = builder.body(Body::from(""))?;
-            // .body(Body::from(serde_json::to_string(&data)?))?;
+        let request: Request<Body> = self.builder(Method::DELETE, endpoint).body(Body::from(""))?;
         let res: Response<Body> = self.client.request(request).await?;
         self.handler(res).await
     }
 
     pub async fn delete_many<T: DeserializeOwned, K: Serialize>(&self, endpoint: String, data: &K) -> Result<T> {
-        let url: String = format!("{}{}", self.to_base(), endpoint);
+        let request: Request<Body> = self.with_body(self.builder(Method::DELETE, endpoint), data)?;
+        let res: Response<Body> = self.client.request(request).await?;
+        self.handler(res).await
+    }
 
-        let mut builder: Builder = Request::builder()
-            .method(Method::DELETE)
-            .uri(url);
-        {
-            match builder.headers_mut() {
-                Some(h) => self.apply_authentication(h),
-                _ => {}
+    /// Post a streamed JSON body.
+    /// The body is sent as it is produced, `encoding` declares a body that is already compressed.
+    pub async fn post_body<T: DeserializeOwned>(&self, endpoint: String, body: Body, encoding: Option<Encoding>) -> Result<T> {
+        let mut builder: Builder = self.builder(Method::POST, endpoint);
+        if let Some(h) = builder.headers_mut() {
+            h.insert(CONTENT_TYPE, HeaderValue::from_static(ContentType::Json.mime()));
+            if let Some(encoding) = encoding {
+                h.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding.name()));
             }
         }
-        let request: Request<Body> = builder.body(Body::from(serde_json::to_string(&data)?))?;
-        let res: Response<Body> = self.client.request(request).await?;
+        let res: Response<Body> = self.client.request(builder.body(body)?).await?;
         self.handler(res).await
     }
 
+    /// Stream the elements of an array shaped response body.
+    /// `field` selects a top level attribute of an object body (e.g. "result" of a cursor),
+    /// None streams a body that is an array itself.
+    /// Streamed responses are always requested as JSON.
+    pub async fn get_stream<T: DeserializeOwned>(&self, endpoint: String, field: Option<&str>) -> Result<ArrayStream<T>> {
+        let request: Request<Body> = self.stream_builder(Method::GET, endpoint).body(hyper::Body::default())?;
+        self.stream(request, field).await
+    }
+
+    pub async fn post_stream<T: DeserializeOwned, K: Serialize>(&self, endpoint: String, data: &K, field: Option<&str>) -> Result<ArrayStream<T>> {
+        let request: Request<Body> = self.with_body(self.stream_builder(Method::POST, endpoint), data)?;
+        self.stream(request, field).await
+    }
+
+    pub async fn put_stream<T: DeserializeOwned, K: Serialize>(&self, endpoint: String, data: &K, field: Option<&str>) -> Result<ArrayStream<T>> {
+        let request: Request<Body> = self.with_body(self.stream_builder(Method::PUT, endpoint), data)?;
+        self.stream(request, field).await
+    }
+
+    fn stream_builder(&self, method: Method, endpoint: String) -> Builder {
+        let mut builder: Builder = self.builder(method, endpoint);
+        if let Some(h) = builder.headers_mut() {
+            h.insert(ACCEPT, HeaderValue::from_static(ContentType::Json.mime()));
+        }
+        builder
+    }
+
+    pub(crate) async fn send(&self, request: Request<Body>) -> Result<Response<Body>> {
+        Ok(self.client.request(request).await?)
+    }
+
+    async fn stream<T: DeserializeOwned>(&self, request: Request<Body>, field: Option<&str>) -> Result<ArrayStream<T>> {
+        let res: Response<Body> = self.client.request(request).await?;
+        if !res.status().is_success() {
+            return Err(self.failure(res).await);
+        }
+        let encoding: Option<Encoding> = Encoding::from_headers(res.headers());
+        Ok(ArrayStream::new(res.into_body(), encoding, field))
+    }
+
     async fn handler<T: DeserializeOwned>(&self, res: Response<Body>) -> Result<T> {
         if res.status().is_success() {
+            // the server answers in the negotiated content type
+            let content_type: ContentType = ContentType::from_headers(res.headers());
+            let encoding: Option<Encoding> = Encoding::from_headers(res.headers());
             let body = hyper::body::aggregate(res).await?;
-            let data: T = serde_json::from_reader(body.reader())?;
-            return Ok(data);
+            return Self::decode(content_type, encoding, body);
+        }
+        Err(self.failure(res).await)
+    }
+
+    /// Decode the ApiError of an unsuccessful response.
+    pub(crate) async fn failure(&self, res: Response<Body>) -> ClientError {
+        let content_type: ContentType = ContentType::from_headers(res.headers());
+        let encoding: Option<Encoding> = Encoding::from_headers(res.headers());
+        let body = match hyper::body::aggregate(res).await {
+            Ok(body) => body,
+            Err(e) => return e.into(),
+        };
+        match Self::decode::<ApiError, _>(content_type, encoding, body) {
+            Ok(err) => err.into(),
+            Err(e) => e,
+        }
+    }
+
+    pub(crate) fn decode<T: DeserializeOwned, B: Buf>(content_type: ContentType, encoding: Option<Encoding>, body: B) -> Result<T> {
+        let mut reader: Box<dyn Read> = match encoding {
+            Some(encoding) => encoding.decoder(body.reader()),
+            None => Box::new(body.reader()),
+        };
+        match content_type {
+            ContentType::Json => Ok(serde_json::from_reader(reader)?),
+            ContentType::VelocyPack => {
+                let mut bytes: Vec<u8> = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                Ok(vpack::from_slice(&bytes)?)
+            },
         }
-        let body = hyper::body::aggregate(res).await?;
-        let err: ApiError = serde_json::from_reader(body.reader())?;
-        Err(err.into())
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::io::Read;
+
     use serde::Deserialize;
     use crate::Client;
 
-    use super::Config;
+    use super::{Config, Encoding, DocumentRouter, Router};
     
     #[derive(Deserialize)]
     pub struct Fake;
 
+    #[test]
+    fn test_encoded_key() {
+        assert!(Router::key("user", "a b/c?d").ends_with("/user/a%20b%2Fc%3Fd"));
+        assert!(Router::key("user", "a:b@c").ends_with("/user/a:b@c"));
+    }
+
     #[tokio::test]
     async fn test_invalid_uri() {
         let client: Client = Client::new(&Config{
@@ -457,4 +696,18 @@ mod test {
             Err(_) => {}
         }
     }
+
+    #[test]
+    fn test_compression_round_trip() {
+        let data: Vec<u8> = br#"{"body": "compressible"}"#.repeat(100);
+
+        for encoding in [Encoding::Gzip, Encoding::Deflate] {
+            let compressed: Vec<u8> = encoding.compress(&data, 6).unwrap();
+            assert!(compressed.len() < data.len());
+
+            let mut decompressed: Vec<u8> = Vec::new();
+            encoding.decoder(compressed.as_slice()).read_to_end(&mut decompressed).unwrap();
+            assert_eq!(decompressed, data);
+        }
+    }
 }
\ No newline at end of file
@@ -1,11 +1,15 @@
 use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
     Result, Client, 
     Router, 
     IdResponse, CollectionRouter, 
     api::{API, self}, 
-    Response, Empty, FlatResponse
+    Response, Empty, FlatResponse,
+    Query, CursorRequest, CursorStream, Options, Filter, filter::Bindings,
+    Metadata, Timestamps
 };
 
 
@@ -45,8 +49,12 @@ pub struct ComputedValue{
 pub struct KeyOptions {
     pub allow_user_keys: bool,
     pub r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub increment: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub offset: Option<u32>,
+    /// Not reported by cluster deployments
+    #[serde(default)]
     pub last_value: u32
 }
 
@@ -101,32 +109,9 @@ pub struct NewCollection {
 
 impl NewCollection{
 
+    /// Timestamps in milliseconds
     pub fn default_computed_values() -> Vec<ComputedValue> {
-        vec![
-            ComputedValue{
-                name: "created_on".to_owned(),
-                expression: "RETURN DATE_NOW()".to_owned(),
-                compute_on: vec![
-                    "insert".to_owned(),
-                    "replace".to_owned()
-                ],
-                overwrite: true,
-                keep_null: false,
-                fail_on_warning: true,
-            },
-            ComputedValue{
-                name: "modified_on".to_owned(),
-                expression: "RETURN DATE_NOW()".to_owned(),
-                compute_on: vec![
-                    "insert".to_owned(),
-                    "update".to_owned(),
-                    "replace".to_owned(),
-                ],
-                overwrite: true,
-                keep_null: false,
-                fail_on_warning: true,
-            }
-        ]
+        <Timestamps>::computed_values()
     }
 
     pub fn default_document_collection(name: String) -> Self{
@@ -252,7 +237,7 @@ pub struct Summary {
     pub figures: Figures
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Properties {
     pub id: String,
@@ -321,6 +306,41 @@ pub struct Rename {
     pub name: String
 }
 
+/// Layout of an export
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// One JSON document per line
+    #[default]
+    JsonLines,
+    /// A single JSON array of documents
+    Array
+}
+
+#[derive(Debug)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// Documents fetched per round trip
+    pub batch_size: u32,
+    /// Attributes to keep, None exports whole documents
+    pub projection: Option<Vec<String>>,
+    /// Condition on the exported documents, its values are bound
+    pub filter: Option<Filter>,
+    /// Lifetime of the cursor between two batches in seconds
+    pub ttl: Option<u32>
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::JsonLines,
+            batch_size: 1000,
+            projection: None,
+            filter: None,
+            ttl: None
+        }
+    }
+}
+
 pub struct Collection {
     pub client: Client,
     pub name: String
@@ -462,6 +482,70 @@ impl Collection
         let response: FlatResponse<Information> = self.client.put(endpoint, &Empty{}).await?;
         Ok(response.result)
     }
+
+    /// Export every document of the collection into `writer`.
+    /// Documents are read through a streaming cursor, the next batch is only
+    /// requested once the writer has accepted the previous one.
+    /// Returns the number of exported documents.
+    pub async fn export<W: AsyncWrite + Unpin>(&self, writer: &mut W, options: &ExportOptions) -> Result<u64> {
+        let query: Query = Query { client: self.client.clone() };
+        let mut cursor: CursorStream<Value> = query.cursor_stream(&self.export_request(options)).await?;
+
+        let exported: Result<u64> = Self::write_export(&mut cursor, writer, options).await;
+        if exported.is_err() {
+            // the export error is returned, the cursor expires with its ttl if it cannot be deleted
+            let _ = cursor.close().await;
+        }
+        exported
+    }
+
+    async fn write_export<W: AsyncWrite + Unpin>(cursor: &mut CursorStream<Value>, writer: &mut W, options: &ExportOptions) -> Result<u64> {
+        let mut exported: u64 = 0;
+        if options.format == ExportFormat::Array {
+            writer.write_all(b"[").await?;
+        }
+        while let Some(document) = cursor.next().await? {
+            if options.format == ExportFormat::Array && exported > 0 {
+                writer.write_all(b",").await?;
+            }
+            writer.write_all(&serde_json::to_vec(&document)?).await?;
+            if options.format == ExportFormat::JsonLines {
+                writer.write_all(b"\n").await?;
+            }
+            exported += 1;
+        }
+        if options.format == ExportFormat::Array {
+            writer.write_all(b"]").await?;
+        }
+        writer.flush().await?;
+        Ok(exported)
+    }
+
+    fn export_request(&self, options: &ExportOptions) -> CursorRequest {
+        let mut bindings: Bindings = Bindings::default();
+        let mut query: String = format!("FOR doc IN {}", bindings.collection(&self.name));
+        if let Some(filter) = &options.filter {
+            query.push_str(&format!(" FILTER {}", filter.render("doc", &mut bindings)));
+        }
+        match &options.projection {
+            Some(attributes) => {
+                let attributes: String = bindings.value(Value::from(attributes.clone()));
+                query.push_str(&format!(" RETURN KEEP(doc, {})", attributes));
+            },
+            None => query.push_str(" RETURN doc"),
+        }
+
+        CursorRequest {
+            query,
+            // not supported by streaming cursors
+            count: false,
+            batch_size: Some(options.batch_size),
+            ttl: options.ttl,
+            bind: Some(bindings.into_inner()),
+            options: Some(Options { stream: Some(true), ..Default::default() }),
+            ..Default::default()
+        }
+    }
 }
 
 /*
@@ -474,4 +558,38 @@ impl Collection
         Ok(res)
     }
 
-*/
\ No newline at end of file
+*/
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use crate::{Client, Config, Collection, ExportOptions, Filter};
+
+    #[test]
+    fn test_export_request() {
+        let client: Client = Client::new(&Config {
+            host: "http://localhost:8529".to_owned(),
+            database: "fake".to_owned(),
+            user: String::new(),
+            password: String::new(),
+        });
+        let collection: Collection = Collection::new(client, "sample".to_owned());
+
+        let options: ExportOptions = ExportOptions {
+            projection: Some(vec!["_key".to_owned(), "body".to_owned()]),
+            filter: Some(Filter::ne("body", Value::Null)),
+            ..Default::default()
+        };
+
+        let request = serde_json::to_value(collection.export_request(&options)).unwrap();
+        assert_eq!(request, json!({
+            "id": null,
+            "query": "FOR doc IN @@collection FILTER doc.@attribute1 != @value2 RETURN KEEP(doc, @value3)",
+            "count": false,
+            "batchSize": 1000,
+            "cache": false,
+            "bindVars": { "@collection": "sample", "attribute1": "body", "value2": null, "value3": ["_key", "body"] },
+            "options": { "stream": true }
+        }));
+    }
+}
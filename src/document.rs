@@ -1,25 +1,211 @@
+use std::{any::{Any, TypeId}, collections::{BTreeMap, HashMap, HashSet}, marker::PhantomData, sync::{Arc, Mutex}};
+
 use serde::{Serialize, de::DeserializeOwned, Deserialize};
+use serde_json::{json, Map, Value};
 
 use crate::{
-    DocumentQueryParams, 
+    DocumentQueryParams, OverwriteMode,
     Result, Client, DocumentRouter,
-    Empty, Router, ArangoKeys, name, ArangoEdgeKeys
+    Empty, Router, ArangoKeys, name, ArangoEdgeKeys, ArrayStream, Patch,
+    Method, StatusCode, RequestBuilder, RawResponse, CacheOptions, cache::{Cached, DocumentCache}, Query, filter::Bindings,
+    Collection, Information, ClientError, AsKey, AsId, DocumentId, DocumentKey, DocumentRevision,
+    Metadata, Timestamps, hooks::{Hooked, Written}, api::ApiError
 };
 
 #[derive(Deserialize, Debug)]
 pub struct DocumentResponse<D> {
-    // #[serde(rename="new")]
+    /// Present when WriteOptions::return_new is set
     pub new: Option<D>,
 
-    // #[serde(rename="old")]
+    /// Present when WriteOptions::return_old is set
     pub old: Option<D>,
 }
 
+/// Options of a single document write
+#[derive(Clone, Debug)]
+pub struct WriteOptions {
+    /// Wait until the write is synced to disk
+    pub wait_for_sync: bool,
+    pub return_new: bool,
+    pub return_old: bool,
+    /// Return an empty response, overrides return_new and return_old
+    pub silent: bool,
+    pub refill_index_caches: Option<bool>,
+    /// Updates only: false removes attributes set to null
+    pub keep_null: Option<bool>,
+    /// Updates only: false replaces nested objects instead of merging them
+    pub merge_objects: Option<bool>
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            wait_for_sync: true,
+            return_new: true,
+            return_old: false,
+            silent: false,
+            refill_index_caches: None,
+            keep_null: None,
+            merge_objects: None
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Fire and forget, nothing is synced nor returned
+    pub fn fast() -> Self {
+        Self {
+            wait_for_sync: false,
+            return_new: false,
+            silent: true,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn params(&self) -> DocumentQueryParams {
+        DocumentQueryParams {
+            wait_for_sync: self.wait_for_sync,
+            return_new: self.return_new.then_some(true),
+            return_old: self.return_old.then_some(true),
+            silent: self.silent.then_some(true),
+            refill_index_cache: self.refill_index_caches,
+            keep_null: self.keep_null,
+            merge_objects: self.merge_objects,
+            ..Default::default()
+        }
+    }
+}
+
+/// Options of Document::insert_with_options
+#[derive(Clone, Debug, Default)]
+pub struct InsertOptions {
+    pub overwrite_mode: Option<OverwriteMode>,
+    /// keep_null and merge_objects only apply to OverwriteMode::Update
+    pub write: WriteOptions
+}
+
+impl InsertOptions {
+    fn params(&self) -> DocumentQueryParams {
+        let mut params: DocumentQueryParams = self.write.params();
+        params.overwrite_mode = self.overwrite_mode;
+        if self.overwrite_mode != Some(OverwriteMode::Update) {
+            params.keep_null = None;
+            params.merge_objects = None;
+        }
+        params
+    }
+}
+
+/// Options of Document::insert_edge
+#[derive(Clone, Debug, Default)]
+pub struct EdgeOptions {
+    /// Check that both vertices exist before inserting the edge
+    pub verify_vertices: bool,
+    /// return_new is always set, the inserted edge is returned
+    pub write: WriteOptions
+}
+
+/// Meta attributes of a written document
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct WriteMeta {
+    #[serde(rename = "_id")]
+    pub id: DocumentId,
+
+    #[serde(rename = "_key")]
+    pub key: DocumentKey,
+
+    #[serde(rename = "_rev")]
+    pub rev: DocumentRevision,
+
+    /// Revision before an update, replace or overwriting insert
+    #[serde(rename = "_oldRev")]
+    pub old_rev: Option<DocumentRevision>
+}
+
+/// Outcome of a document write
+#[derive(Deserialize, Debug)]
+pub struct WriteResult<R, M = Timestamps>
+where 
+    R: Sized + Sync + Send {
+
+    /// None for silent writes
+    #[serde(flatten)]
+    pub meta: Option<WriteMeta>,
+
+    /// Present when WriteOptions::return_new is set, None when an insert ignored an existing document
+    pub new: Option<Doc<R, M>>,
+
+    /// Present when WriteOptions::return_old is set
+    pub old: Option<Doc<R, M>>
+}
+
+impl<R, M> WriteResult<R, M>
+where 
+    R: Sized + Sync + Send {
+    /// Inserts only: true if no document existed under the key.
+    /// Requires WriteOptions::return_new to tell created and ignored documents apart.
+    pub fn created(&self) -> bool {
+        self.new.is_some() && self.meta.as_ref().is_some_and(|meta| meta.old_rev.is_none())
+    }
+
+    /// Revision written, None for silent writes
+    pub fn rev(&self) -> Option<&DocumentRevision> {
+        self.meta.as_ref().map(|meta| &meta.rev)
+    }
+}
+
+/// Outcome of a conditional read
+#[derive(Debug)]
+pub enum Conditional<R, M = Timestamps>
+where 
+    R: Sized + Sync + Send {
+    Modified(Doc<R, M>),
+    /// The known revision is still current
+    NotModified
+}
+
+/// errorNum of a document that does not exist
+const DOCUMENT_NOT_FOUND: i32 = 1202;
+
+/// Revision in the ETag header of a document response, without its quotes
+fn etag(response: &RawResponse) -> Result<DocumentRevision> {
+    match response.header("etag").map(|value| value.trim_matches('"')) {
+        Some(rev) if !rev.is_empty() => Ok(DocumentRevision::from(rev)),
+        _ => Err(ClientError::Api(ApiError {
+            code: response.status.as_u16() as i32,
+            error: true,
+            error_message: "document response without an ETag header".to_owned(),
+            error_num: 0
+        })),
+    }
+}
+
+/// Record with an explicit _key
+#[derive(Serialize)]
+struct Keyed<'a, R> {
+    #[serde(rename = "_key")]
+    key: &'a str,
+
+    #[serde(flatten)]
+    record: &'a R
+}
+
+/// Record with attributes outside of R
+#[derive(Serialize)]
+struct Extended<'a, R> {
+    #[serde(flatten)]
+    record: Hooked<'a, R>,
+
+    #[serde(flatten)]
+    attributes: Map<String, Value>
+}
+
 /*
     Generic Document struct 
+    M is the metadata policy, see Timestamps and NoMetadata
 */
 #[derive(Serialize, Deserialize, Debug)]
-pub struct Doc<R> 
+pub struct Doc<R, M = Timestamps> 
 where 
     R: Sized + Sync + Send {
 
@@ -29,11 +215,11 @@ where
     #[serde(flatten)]
     pub record: R,
 
-    pub modified_on: u64,
-    pub created_on: u64
+    #[serde(flatten)]
+    pub metadata: M
 }
 
-impl<R> Doc<R>
+impl<R, M> Doc<R, M>
 where 
     R: Serialize + DeserializeOwned + Sync + Send {
     /// helper function to get the naming scheme for record <R>.
@@ -42,84 +228,419 @@ where
     }
 }
 
+impl<R, M> AsId for Doc<R, M>
+where
+    R: Sync + Send {
+    fn as_id(&self) -> &DocumentId {
+        &self.keys.id
+    }
+}
+
+
+/// Document API, M is the metadata policy of the documents it reads and writes
+pub struct Document<M = Timestamps> {
+    pub client: Client,
+    cache: Option<Arc<DocumentCache>>,
+    /// Hooks by TypeId of their record
+    pub(crate) hooks: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    /// Collections known to be edge collections
+    edge_collections: Mutex<HashSet<String>>,
+    metadata: PhantomData<fn() -> M>
+}
 
-pub struct Document{
-    pub client: Client
+impl<M> From<Client> for Document<M> {
+    fn from(client: Client) -> Self {
+        Self { client, cache: None, hooks: HashMap::new(), edge_collections: Mutex::default(), metadata: PhantomData }
+    }
 }
 
-impl Document {
+impl<M: Metadata> Document<M> {
+    /// The same handle for documents with the metadata policy N
+    /// ```ignore
+    /// let plain: Document<NoMetadata> = Document::from(client).with_metadata();
+    /// ```
+    pub fn with_metadata<N: Metadata>(self) -> Document<N> {
+        Document { client: self.client, cache: self.cache, hooks: self.hooks, edge_collections: self.edge_collections, metadata: PhantomData }
+    }
+
+    /// Serve read and read_many from a bounded LRU cache.
+    /// Entries older than the ttl are revalidated by revision,
+    /// writes through this handle invalidate the written keys.
+    pub fn with_cache(mut self, options: CacheOptions) -> Self {
+        self.cache = Some(Arc::new(DocumentCache::new(options)));
+        self
+    }
+
+    /// Drop a cached document
+    pub(crate) fn invalidate<R>(&self, key: &str) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(&name::<R>(), key);
+        }
+    }
 
     /// Insert document into the collection
-    pub async fn insert<R: Serialize + DeserializeOwned + Sync + Send>(&self, model: &R) -> Result<Doc<R>>{
-        let response: DocumentResponse<Doc<R>> = self.client.post(Router::base_with_params(&name::<R>(), DocumentQueryParams::default()).unwrap(), &model).await?;
-        Ok(response.new.unwrap())
+    pub async fn insert<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, model: &R, options: &WriteOptions) -> Result<WriteResult<R, M>>{
+        self.insert_extended(model, Map::new(), options.params()).await
+    }
+
+    /// Insert document into the collection
+    /// The overwrite mode decides what happens to an existing document with the same _key.
+    pub async fn insert_with_options<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, model: &R, options: &InsertOptions) -> Result<WriteResult<R, M>>{
+        self.insert_extended(model, Map::new(), options.params()).await
+    }
+
+    /// Insert a record together with attributes that are not part of R, e.g. a foreign key
+    pub(crate) async fn insert_extended<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, model: &R, attributes: Map<String, Value>, params: DocumentQueryParams) -> Result<WriteResult<R, M>>{
+        let record: Extended<R> = Extended { record: self.run_before_insert(model)?, attributes };
+        let result: WriteResult<R, M> = self.client.post(Router::base_with_params(&name::<R>(), params)?, &record).await?;
+        if let Some(meta) = &result.meta {
+            self.invalidate::<R>(&meta.key);
+        }
+        self.run_after_insert(&Written::of(Some(record.record.record()), &result));
+        Ok(result)
+    }
+
+    /// Insert a document under key, or merge the record into the existing one.
+    /// Merging keeps the created_on of the existing document.
+    /// The overwrite mode of options is always OverwriteMode::Update.
+    pub async fn upsert<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, key: &(impl AsKey + ?Sized), model: &R, options: &InsertOptions) -> Result<WriteResult<R, M>>{
+        let key: &str = key.key_in(&name::<R>())?;
+        let options: InsertOptions = InsertOptions { overwrite_mode: Some(OverwriteMode::Update), ..options.clone() };
+        let record: Hooked<R> = self.run_before_insert(model)?;
+        let result: Result<WriteResult<R, M>> = self.client.post(Router::base_with_params(&name::<R>(), options.params())?, &Keyed { key, record: &record }).await;
+        self.invalidate::<R>(key);
+
+        let result: WriteResult<R, M> = result?;
+        self.run_after_insert(&Written::of(Some(record.record()), &result));
+        Ok(result)
+    }
+
+    /// Insert an edge between two vertices, `_from` and `_to` are taken from their `_id`s.
+    /// Vertices are documents of any metadata policy, or their DocumentId.
+    /// Fails with a validation error if the collection of R is not an edge collection,
+    /// or if a vertex does not exist when EdgeOptions::verify_vertices is set.
+    pub async fn insert_edge<R>(&self, from: &(impl AsId + ?Sized), to: &(impl AsId + ?Sized), data: R, options: &EdgeOptions) -> Result<Doc<Edge<R>, M>>
+    where
+        R: Serialize + DeserializeOwned + Sync + Send + 'static {
+        self.edge_collection(&name::<R>()).await?;
+
+        let (from, to): (&DocumentId, &DocumentId) = (from.as_id(), to.as_id());
+        if options.verify_vertices {
+            for id in [from, to] {
+                if !self.exists(id).await? {
+                    return Err(ClientError::validation_error(format!("vertex {} does not exist", id)));
+                }
+            }
+        }
+
+        let write: WriteOptions = WriteOptions { return_new: true, silent: false, ..options.write.clone() };
+        let edge: Edge<R> = Edge::new(from.clone(), to.clone(), data);
+        let result: WriteResult<Edge<R>, M> = self.insert(&edge, &write).await?;
+        result.new.ok_or_else(|| ClientError::insertion_error(format!("edge into {} was not returned", name::<R>())))
+    }
+
+    /// Fails with a validation error unless collection is an edge collection.
+    /// The type is looked up once per collection and handle.
+    pub(crate) async fn edge_collection(&self, collection: &str) -> Result<()> {
+        if self.edge_collections.lock().unwrap_or_else(|e| e.into_inner()).contains(collection) {
+            return Ok(());
+        }
+        let information: Information = Collection::new(self.client.clone(), collection.to_owned()).information().await?;
+        if information.r#type != 3 {
+            return Err(ClientError::validation_error(format!("{} is not an edge collection", information.name)));
+        }
+        self.edge_collections.lock().unwrap_or_else(|e| e.into_inner()).insert(collection.to_owned());
+        Ok(())
+    }
+
+    /// Whether the document with the `_id` exists
+    pub(crate) async fn exists(&self, id: &DocumentId) -> Result<bool> {
+        let response: RawResponse = self.client.request(Method::HEAD)
+            .path("_api")
+            .path("document")
+            .path(&id.collection)
+            .path(&id.key)
+            .send()
+            .await?;
+        if response.status == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        response.error_for_status()?;
+        Ok(true)
     }
 
     /// Insert many documents into the collection
-    pub async fn insert_many<R: Serialize + DeserializeOwned + Sync + Send>(&self, models: &Vec<R>) -> Result<Vec<ArangoKeys>>{
+    pub async fn insert_many<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, models: &[R]) -> Result<Vec<ArangoKeys>>{
         let route: String = format!("{}#multiple", Router::base(&name::<R>()));
         println!("ROUTE: {:#?}", route);
-        let response: Vec<ArangoKeys> = self.client.post(route, &models).await?;
+        let records: Vec<Hooked<R>> = models.iter()
+            .map(|model| self.run_before_insert(model))
+            .collect::<Result<_>>()?;
+        let response: Vec<ArangoKeys> = self.client.post(route, &records).await?;
+        if self.hooks_of::<R>().is_some() {
+            for (keys, record) in response.iter().zip(&records) {
+                let meta: WriteMeta = WriteMeta { id: keys.id.clone(), key: keys.key.clone(), rev: keys.rev.clone(), old_rev: None };
+                self.run_after_insert(&Written { record: Some(record.record()), meta: Some(&meta), new: None, old: None });
+            }
+        }
         Ok(response)
     }
 
     /// Read a document by key
-    pub async fn read<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &str) -> Result<Doc<R>>{
-        Ok(self.client.get(Router::key(&name::<R>(), key)).await?)
+    pub async fn read<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized)) -> Result<Doc<R, M>>{
+        let key: &str = key.key_in(&name::<R>())?;
+        let cache: &DocumentCache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.client.get(Router::key(&name::<R>(), key)).await,
+        };
+        let collection: String = name::<R>();
+
+        if let Some(cached) = cache.get(&collection, key) {
+            if cached.fresh {
+                return Ok(serde_json::from_value(cached.document)?);
+            }
+            let revalidated: Result<Option<Value>> = self.fetch_if_changed::<R>(key, &cached.rev).await;
+            // a deleted document must not be revalidated again by every read
+            if matches!(&revalidated, Err(ClientError::Api(error)) if error.code == StatusCode::NOT_FOUND.as_u16() as i32) {
+                cache.invalidate(&collection, key);
+            }
+            match revalidated? {
+                None => {
+                    cache.revalidated(&collection, key);
+                    return Ok(serde_json::from_value(cached.document)?);
+                },
+                Some(document) => return Self::cached(cache, &collection, document),
+            }
+        }
+
+        let document: Value = self.client.get(Router::key(&collection, key)).await?;
+        Self::cached(cache, &collection, document)
     }
 
     /// Read multiple documents by key
-    pub async fn read_many<S: Serialize + Sized + Sync + Send, R: Serialize + DeserializeOwned + Sync + Send>(&self, keys: &Vec<S>) -> Result<Vec<Doc<R>>> {
-        let response: Vec<Doc<R>> = self.client.put(Router::base(&name::<R>()),keys).await?;
-        Ok(response)
+    /// With a cache stale entries are revalidated by revision in a single query,
+    /// only the keys without a current entry are requested.
+    pub async fn read_many<S: Serialize + Sized + Sync + Send, R: Serialize + DeserializeOwned + Sync + Send>(&self, keys: &[S]) -> Result<Vec<Doc<R, M>>> {
+        let cache: &DocumentCache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.client.put(Router::read_many(&name::<R>()), &keys).await,
+        };
+        let collection: String = name::<R>();
+
+        // documents selected by object (e.g. {"_key": ..}) bypass the cache
+        let keys: Vec<String> = match keys.iter().map(|key| match serde_json::to_value(key) {
+            Ok(Value::String(key)) => Some(key),
+            _ => None,
+        }).collect() {
+            Some(keys) => keys,
+            None => return self.client.put(Router::read_many(&collection), &keys).await,
+        };
+
+        let mut documents: Vec<Option<Value>> = vec![];
+        let mut stale: Vec<(usize, Cached)> = vec![];
+        for (index, key) in keys.iter().enumerate() {
+            match cache.get(&collection, key) {
+                Some(cached) if cached.fresh => documents.push(Some(cached.document)),
+                Some(cached) => {
+                    stale.push((index, cached));
+                    documents.push(None);
+                },
+                None => documents.push(None),
+            }
+        }
+
+        if !stale.is_empty() {
+            let current: Vec<bool> = self.unchanged(&collection, stale.iter().map(|(index, cached)| (&keys[*index], &cached.rev))).await?;
+            for ((index, cached), current) in stale.into_iter().zip(current) {
+                if current {
+                    cache.revalidated(&collection, &keys[index]);
+                    documents[index] = Some(cached.document);
+                }
+            }
+        }
+
+        let missing: Vec<&String> = keys.iter()
+            .zip(&documents)
+            .filter(|(_, document)| document.is_none())
+            .map(|(key, _)| key)
+            .collect();
+
+        if !missing.is_empty() {
+            let fetched: Vec<Value> = self.client.put(Router::read_many(&collection), &missing).await?;
+            let mut fetched = fetched.into_iter();
+            for slot in documents.iter_mut().filter(|document| document.is_none()) {
+                if let Some(document) = fetched.next() {
+                    Self::remember(cache, &collection, &document);
+                    *slot = Some(document);
+                }
+            }
+        }
+
+        documents.into_iter()
+            .map(|document| Ok(serde_json::from_value(document.unwrap_or_default())?))
+            .collect()
+    }
+
+    /// Whether each key still has the revision, missing documents have none
+    async fn unchanged<'a>(&self, collection: &str, revisions: impl Iterator<Item = (&'a String, &'a String)>) -> Result<Vec<bool>> {
+        let revisions: Vec<Value> = revisions.map(|(key, rev)| json!({ "key": key, "rev": rev })).collect();
+        let mut bindings: Bindings = Bindings::default();
+        let query: String = format!(
+            "FOR cached IN {} RETURN DOCUMENT({}, cached.key)._rev == cached.rev",
+            bindings.value(Value::from(revisions)),
+            bindings.value(Value::from(collection))
+        );
+        Query { client: self.client.clone() }.collect(&bindings.request(query)).await
+    }
+
+    /// Keep a fetched document in the cache, errors of read_many are not cached.
+    fn remember(cache: &DocumentCache, collection: &str, document: &Value) {
+        if let (Some(key), Some(rev)) = (document.get("_key").and_then(Value::as_str), document.get("_rev").and_then(Value::as_str)) {
+            cache.put(collection, key, rev, document.clone());
+        }
+    }
+
+    fn cached<R: DeserializeOwned + Sync + Send>(cache: &DocumentCache, collection: &str, document: Value) -> Result<Doc<R, M>> {
+        Self::remember(cache, collection, &document);
+        Ok(serde_json::from_value(document)?)
+    }
+
+    /// Read a document of any collection by `_id`, R only decides the record type
+    pub async fn read_by_id<R: Serialize + DeserializeOwned + Sync + Send>(&self, id: &DocumentId) -> Result<Doc<R, M>>{
+        self.client.get(Router::key(&id.collection, &id.key)).await
+    }
+
+    /// Resolve references to documents of several collections.
+    /// The ids are grouped by collection, each collection is read with a single request.
+    /// Ids of missing documents are left out of the result, any other failure is an error.
+    pub async fn read_ids(&self, ids: &[DocumentId]) -> Result<HashMap<DocumentId, Value>>{
+        let mut collections: BTreeMap<&str, Vec<&DocumentKey>> = BTreeMap::new();
+        for id in ids {
+            collections.entry(&id.collection).or_default().push(&id.key);
+        }
+
+        let mut documents: HashMap<DocumentId, Value> = HashMap::with_capacity(ids.len());
+        for (collection, keys) in collections {
+            let response: Vec<Value> = self.client.put(Router::read_many(collection), &keys).await?;
+            // failed documents are returned as error objects in place
+            for (key, document) in keys.into_iter().zip(response) {
+                if document.get("error").and_then(Value::as_bool) != Some(true) {
+                    documents.insert(DocumentId { collection: collection.to_owned(), key: key.clone() }, document);
+                    continue;
+                }
+                let error_num: i32 = document.get("errorNum").and_then(Value::as_i64).unwrap_or_default() as i32;
+                if error_num != DOCUMENT_NOT_FOUND {
+                    return Err(ClientError::Api(ApiError {
+                        code: document.get("code").and_then(Value::as_i64).unwrap_or_default() as i32,
+                        error: true,
+                        error_message: document.get("errorMessage").and_then(Value::as_str).unwrap_or_default().to_owned(),
+                        error_num
+                    }));
+                }
+            }
+        }
+        Ok(documents)
+    }
+
+    /// Read multiple documents by key
+    /// Documents are deserialized one at a time as the response arrives.
+    pub async fn read_many_stream<S: Serialize + Sized + Sync + Send, R: Serialize + DeserializeOwned + Sync + Send>(&self, keys: &Vec<S>) -> Result<ArrayStream<Doc<R, M>>> {
+        self.client.put_stream(Router::read_many(&name::<R>()), keys, None).await
     }
 
     /// Read a document header by key
     /// Use this method to review the status of a document.
-    /// Returns 200 empty response if document exists
-    /// Returns 404 if document does not exist
-    /// The ArangoDB endpoint allows further customization using headers which is not currently supported.
-    pub async fn read_header<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &str) -> Result<()>{
-        let _ : Empty = self.client.head(Router::key(&name::<R>(), key)).await?;
-        Ok(())
+    /// Returns the current _rev if the document exists
+    /// Fails with a 404 ApiError if the document does not exist
+    pub async fn read_header<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized)) -> Result<DocumentRevision>{
+        let key: &str = key.key_in(&name::<R>())?;
+        let response: RawResponse = self.document_request::<R>(Method::HEAD, key).send().await?.error_for_status()?;
+        etag(&response)
     }
 
-    /// Delete a document by key
-    /// It's recommended to use the instance method Document::delete instead.
-    pub async fn delete<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &str) -> Result<Doc<R>> {
-        let mut params: DocumentQueryParams = DocumentQueryParams::default();
-        params.return_old = Some(true);
-        params.return_new = None;
+    /// Read a document by key unless its revision is still `rev`
+    /// Returns Conditional::NotModified without transferring the document when it did not change.
+    pub async fn read_if_changed<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized), rev: &DocumentRevision) -> Result<Conditional<R, M>>{
+        let key: &str = key.key_in(&name::<R>())?;
+        match self.fetch_if_changed::<R>(key, rev.as_str()).await? {
+            Some(document) => Ok(Conditional::Modified(serde_json::from_value(document)?)),
+            None => Ok(Conditional::NotModified),
+        }
+    }
 
-        let response: DocumentResponse<Doc<R>> = self.client.delete(Router::key_with_params(&name::<R>(), &key, params).unwrap()).await?;
-        Ok(response.old.unwrap())
+    /// The document as JSON, None if its revision is still `rev`
+    async fn fetch_if_changed<R>(&self, key: &str, rev: &str) -> Result<Option<Value>>{
+        let response: RawResponse = self.document_request::<R>(Method::GET, key)
+            .header("if-none-match", &format!("\"{}\"", rev))
+            .send()
+            .await?;
+        if response.status == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.decode()?))
     }
 
-    /// Update a document
-    pub async fn update<R: Serialize + DeserializeOwned + Sync + Send>(&self, doc: &Doc<R>) -> Result<()> {
-        let mut params: DocumentQueryParams = DocumentQueryParams::default();
-        params.silent = Some(true);
-        params.return_new = None;
+    fn document_request<R>(&self, method: Method, key: &str) -> RequestBuilder<'_> {
+        self.client.request(method)
+            .path("_api")
+            .path("document")
+            .path(&name::<R>())
+            .path(key)
+    }
 
-        let _: Empty = self.client.patch(Router::key_with_params(&doc.name(), &doc.keys.key, params).unwrap(), &doc.record).await?;
-        Ok(())
+    /// Delete a document by key
+    /// It's recommended to use the instance method Document::destroy instead.
+    /// Set WriteOptions::return_old to get the deleted document.
+    pub async fn delete<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, key: &(impl AsKey + ?Sized), options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        let key: &str = key.key_in(&name::<R>())?;
+        self.run_before_delete::<R>(key)?;
+        let result: Result<WriteResult<R, M>> = self.client.delete(Router::key_with_params(&name::<R>(), key, options.params())?).await;
+        self.invalidate::<R>(key);
+
+        let result: WriteResult<R, M> = result?;
+        self.run_after_delete(key, &Written::of(None, &result));
+        Ok(result)
+    }
+
+    /// Update a document
+    /// The whole record is sent, use Document::patch to update single attributes.
+    pub async fn update<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        let record: Hooked<R> = self.run_before_update(&doc.keys.key, &doc.record)?;
+        let result: Result<WriteResult<R, M>> = self.client.patch(Router::key_with_params(&doc.name(), &doc.keys.key, options.params())?, &record).await;
+        self.invalidate::<R>(&doc.keys.key);
+
+        let result: WriteResult<R, M> = result?;
+        self.run_after_update(&doc.keys.key, &Written::of(Some(record.record()), &result));
+        Ok(result)
+    }
+    
+    /// Update the attributes of a patch, other attributes are left untouched.
+    /// Nested objects are merged unless WriteOptions::merge_objects is Some(false).
+    pub async fn patch<R: Serialize + DeserializeOwned + Sync + Send + 'static, P: Patch<R>>(&self, key: &(impl AsKey + ?Sized), patch: &P, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        let key: &str = key.key_in(&name::<R>())?;
+        self.run_before_patch::<R>(key, patch)?;
+        let result: Result<WriteResult<R, M>> = self.client.patch(Router::key_with_params(&name::<R>(), key, options.params())?, patch).await;
+        self.invalidate::<R>(key);
+
+        let result: WriteResult<R, M> = result?;
+        self.run_after_update(key, &Written::of(None, &result));
+        Ok(result)
     }
     
     /// Replace a document
-    pub async fn replace<R: Serialize + DeserializeOwned + Sync + Send>(&self, doc: &Doc<R>) -> Result<()> {
-        let mut params: DocumentQueryParams = DocumentQueryParams::default();
-        params.silent = Some(true);
-        params.return_new = None;
-
-        let _: Empty = self.client.put(Router::key_with_params(&doc.name(), &doc.keys.key, params).unwrap(), &doc.record).await?;
-        Ok(())
+    pub async fn replace<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        let record: Hooked<R> = self.run_before_update(&doc.keys.key, &doc.record)?;
+        let result: Result<WriteResult<R, M>> = self.client.put(Router::key_with_params(&doc.name(), &doc.keys.key, options.params())?, &record).await;
+        self.invalidate::<R>(&doc.keys.key);
+
+        let result: WriteResult<R, M> = result?;
+        self.run_after_update(&doc.keys.key, &Written::of(Some(record.record()), &result));
+        Ok(result)
     }
 
     /// Destroy a document
-    pub async fn destroy<R: Serialize + DeserializeOwned + Sync + Send>(&self, doc: &Doc<R>) -> Result<()> {
-        let _: DocumentResponse<Doc<R>> = self.client.delete(
-            Router::key_with_params(&name::<R>(), &doc.keys.key, DocumentQueryParams::default()).unwrap()).await?;
-        Ok(())
+    pub async fn destroy<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>) -> Result<WriteResult<R, M>> {
+        self.delete(&doc.keys.key, &WriteOptions::default()).await
     }    
 
     /// Delete many documents by key
@@ -141,7 +662,7 @@ impl Document {
     }    
 
     /// Update many documents
-    async fn update_many<R: Serialize + DeserializeOwned + Sync + Send>(&self, documents: &Vec<Doc<R>>) -> Result<()> {
+    async fn update_many<R: Serialize + DeserializeOwned + Sync + Send>(&self, documents: &Vec<Doc<R, M>>) -> Result<()> {
         let mut params: DocumentQueryParams = DocumentQueryParams::default();
         params.silent = Some(true);
         params.return_new = None;
@@ -151,7 +672,7 @@ impl Document {
     }
 
     /// Replace many documents
-    async fn replace_many<R: Serialize + DeserializeOwned + Sync + Send>(client: &Client, documents: &Vec<Doc<R>>) -> Result<()> {
+    async fn replace_many<R: Serialize + DeserializeOwned + Sync + Send>(client: &Client, documents: &Vec<Doc<R, M>>) -> Result<()> {
         let mut params: DocumentQueryParams = DocumentQueryParams::default();
         params.silent = Some(true);
         params.return_new = None;
@@ -176,7 +697,7 @@ where
 impl<R> Edge<R> 
 where 
 R: Sized + Sync + Send{
-    pub fn new(from: String, to: String, edge: R) -> Self {
+    pub fn new(from: DocumentId, to: DocumentId, edge: R) -> Self {
         Self {
             link: ArangoEdgeKeys { to, from },
             edge
@@ -187,9 +708,11 @@ R: Sized + Sync + Send{
 
 #[cfg(test)]
 mod test {
-    use serde::Deserialize;
+    use serde::{Serialize, Deserialize};
 
-    use crate::{name, Edge, Doc};
+    use crate::{name, Edge, Doc, InsertOptions, WriteResult, OverwriteMode, WriteOptions};
+
+    use super::Keyed;
 
     #[test]
     fn test_name_scheme() {
@@ -243,4 +766,72 @@ mod test {
 
         // println!("DATA: {:#?}", test_data);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_insert_options() {
+        let mut options: InsertOptions = InsertOptions { 
+            overwrite_mode: Some(OverwriteMode::Replace), 
+            write: WriteOptions { keep_null: Some(false), merge_objects: Some(false), ..Default::default() }
+        };
+        // keep_null and merge_objects only apply to updates
+        assert_eq!(serde_url_params::to_string(&options.params()).unwrap(), "waitForSync=true&returnNew=true&overwriteMode=replace");
+
+        options.overwrite_mode = Some(OverwriteMode::Update);
+        assert_eq!(
+            serde_url_params::to_string(&options.params()).unwrap(), 
+            "waitForSync=true&returnNew=true&overwriteMode=update&keepNull=false&mergeObjects=false"
+        );
+    }
+
+    #[test]
+    fn test_write_options() {
+        let options: WriteOptions = WriteOptions { return_old: true, ..Default::default() };
+        assert_eq!(serde_url_params::to_string(&options.params()).unwrap(), "waitForSync=true&returnNew=true&returnOld=true");
+
+        assert_eq!(serde_url_params::to_string(&WriteOptions::fast().params()).unwrap(), "waitForSync=false&silent=true");
+    }
+
+    #[test]
+    fn test_write_result() {
+        #[derive(Serialize, Deserialize, Debug)]
+        struct MyTestData {
+            pub data: String
+        }
+
+        let keyed: String = serde_json::to_string(&Keyed { key: "1", record: &MyTestData { data: "SOME_DATA".to_owned() } }).unwrap();
+        assert_eq!(keyed, r#"{"_key":"1","data":"SOME_DATA"}"#);
+
+        let data: &str = r#"
+        {
+            "_id": "collection/1",
+            "_key": "1",
+            "_rev": "1235",
+            "_oldRev": "1234",
+            "new": {
+                "_id": "collection/1",
+                "_key": "1",
+                "_rev": "1235",
+                "data": "SOME_DATA",
+                "modified_on": 1,
+                "created_on": 0
+            }
+        }
+        "#;
+        let updated: WriteResult<MyTestData> = serde_json::from_str(data).unwrap();
+        assert!(!updated.created());
+        assert_eq!(updated.meta.as_ref().unwrap().old_rev.as_deref(), Some("1234"));
+        assert_eq!(updated.rev().unwrap(), "1235");
+        assert_eq!(updated.new.unwrap().record.data, "SOME_DATA");
+
+        // ignored
+        let data: &str = r#"{ "_id": "collection/1", "_key": "1", "_rev": "1234" }"#;
+        let ignored: WriteResult<MyTestData> = serde_json::from_str(data).unwrap();
+        assert!(!ignored.created());
+        assert!(ignored.new.is_none());
+
+        // silent
+        let silent: WriteResult<MyTestData> = serde_json::from_str("{}").unwrap();
+        assert!(silent.meta.is_none());
+        assert!(silent.rev().is_none());
+    }
+}
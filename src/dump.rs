@@ -0,0 +1,364 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize, Deserializer};
+use serde_json::Value;
+use tokio::fs::{self, File};
+
+use crate::{
+    Result, Client, Encoding,
+    Router, CollectionRouter, api::{API, self}, FlatResponse,
+    Collection, Database, Index, Import,
+    Information, Properties, PropertiesUpdate, NewCollection, KeyOptions, ComputedValue, Idx, NewIdx,
+    ExportOptions, ImportQueryParams, ImportResponse
+};
+
+/*
+    Logical dump and restore through the HTTP APIs, arangodump compatible:
+    - ENCRYPTION
+    - dump.json              { "database": name, "properties": {..}, "useEnvelope": false }
+    - <name>.structure.json  { "parameters": Parameters, "indexes": [Idx] }
+    - <name>.data.json       one document per line
+
+    Restore also reads the hashed (<name>_<md5>.data.json) and
+    gzip compressed (.data.json.gz) data files written by arangodump.
+*/
+
+/// Indexes maintained by the server itself
+const SYSTEM_INDEXES: [&str; 2] = ["primary", "edge"];
+
+/// Content of `dump.json`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpInfo {
+    pub database: String,
+    /// Properties of the database as reported by the server
+    pub properties: Value,
+    /// Data lines are wrapped in {"type", "data"} markers, only written by old arangodump versions
+    #[serde(default)]
+    pub use_envelope: bool
+}
+
+/// Content of `<name>.structure.json`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Structure {
+    pub parameters: Parameters,
+    pub indexes: Vec<Idx>
+}
+
+/// Collection parameters of a structure file. Only name and type are required,
+/// the remaining attributes differ between server versions and deployments.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameters {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub r#type: u8,
+    #[serde(default)]
+    pub is_system: bool,
+    #[serde(default)]
+    pub wait_for_sync: bool,
+    #[serde(default)]
+    pub cache_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_concern: Option<u8>,
+    /// None on single servers and for satellite collections
+    #[serde(default, deserialize_with = "replication_count", skip_serializing_if = "Option::is_none")]
+    pub replication_factor: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_of_shards: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_keys: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sharding_strategy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_options: Option<KeyOptions>,
+    pub computed_values: Option<Vec<ComputedValue>>
+}
+
+/// replicationFactor is a number, or "satellite"
+fn replication_count<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<u8>, D::Error> {
+    let value: Value = Value::deserialize(deserializer)?;
+    Ok(value.as_u64().and_then(|count| u8::try_from(count).ok()))
+}
+
+#[derive(Debug)]
+pub struct DumpOptions {
+    /// Collections to dump, None dumps every collection
+    pub collections: Option<Vec<String>>,
+    pub include_system: bool,
+    /// Documents fetched per round trip
+    pub batch_size: u32
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self {
+            collections: None,
+            include_system: false,
+            batch_size: 1000
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RestoreOptions {
+    /// Collections to restore, None restores every dumped collection
+    pub collections: Option<Vec<String>>,
+    /// Drop existing collections before restoring them
+    pub overwrite: bool
+}
+
+#[derive(Debug)]
+pub struct DumpedCollection {
+    pub name: String,
+    pub documents: u64
+}
+
+#[derive(Debug)]
+pub struct RestoredCollection {
+    pub name: String,
+    /// None when the dump holds no data file for the collection
+    pub import: Option<ImportResponse>
+}
+
+pub struct Dump {
+    pub client: Client
+}
+
+impl Dump {
+    /// Dump the structure and data of collections into `directory`.
+    pub async fn dump(&self, directory: &Path, options: &DumpOptions) -> Result<Vec<DumpedCollection>> {
+        fs::create_dir_all(directory).await?;
+        fs::write(directory.join("ENCRYPTION"), "none").await?;
+
+        let properties: Value = serde_json::to_value(Database { client: self.client.clone() }.current().await?)?;
+        let info: DumpInfo = DumpInfo {
+            database: properties["name"].as_str().unwrap_or_default().to_owned(),
+            properties,
+            use_envelope: false
+        };
+        fs::write(directory.join("dump.json"), serde_json::to_vec_pretty(&info)?).await?;
+
+        let index: Index = Index { client: self.client.clone() };
+        let export: ExportOptions = ExportOptions { batch_size: options.batch_size, ..Default::default() };
+
+        let mut dumped: Vec<DumpedCollection> = vec![];
+        for name in self.collection_names(options).await? {
+            let collection: Collection = Collection::new(self.client.clone(), name.clone());
+
+            let indexes: Vec<Idx> = index.read(&name).await?
+                .into_iter()
+                .filter(|idx| !SYSTEM_INDEXES.contains(&idx.r#type.as_str()))
+                .collect();
+            let structure: Structure = Structure { parameters: self.parameters(&name).await?, indexes };
+            fs::write(directory.join(format!("{}.structure.json", name)), serde_json::to_vec_pretty(&structure)?).await?;
+
+            let mut file: File = File::create(directory.join(format!("{}.data.json", name))).await?;
+            let documents: u64 = collection.export(&mut file, &export).await?;
+
+            dumped.push(DumpedCollection { name, documents });
+        }
+        Ok(dumped)
+    }
+
+    /// Recreate the collections, data and indexes of a dump.
+    pub async fn restore(&self, directory: &Path, options: &RestoreOptions) -> Result<Vec<RestoredCollection>> {
+        let database: Database = Database { client: self.client.clone() };
+        let index: Index = Index { client: self.client.clone() };
+        let import: Import = Import { client: self.client.clone() };
+
+        let mut restored: Vec<RestoredCollection> = vec![];
+        for structure in Self::structures(directory, options).await? {
+            let Structure { mut parameters, indexes } = structure;
+            let name: String = parameters.name.clone();
+
+            if options.overwrite {
+                _ = database.collection(&name).drop().await;
+            }
+
+            // computed values are enabled once the data is loaded,
+            // otherwise they would overwrite the dumped values.
+            let computed_values = parameters.computed_values.take();
+            let replication_factor: Option<u8> = parameters.replication_factor;
+            let _: Properties = database.new_collection(&new_collection(parameters)).await?;
+
+            let data: Option<ImportResponse> = match Self::data_file(directory, &name).await? {
+                Some((path, encoding)) => {
+                    let file: File = File::open(path).await?;
+                    Some(import.import_encoded(&name, file, encoding, &ImportQueryParams::default()).await?)
+                },
+                None => None,
+            };
+
+            if computed_values.is_some() {
+                let collection: Collection = database.collection(&name);
+                let properties: Properties = collection.properties().await?;
+                let _: Properties = collection.update_properties(&PropertiesUpdate {
+                    wait_for_sync: properties.wait_for_sync,
+                    cache_enabled: properties.cache_enabled,
+                    computed_values,
+                    replication_factor: replication_factor.unwrap_or(1),
+                    write_concern: properties.write_concern
+                }).await?;
+            }
+
+            for idx in indexes {
+                let _ = index.create(&new_index(&name, idx)).await?;
+            }
+
+            restored.push(RestoredCollection { name, import: data });
+        }
+        Ok(restored)
+    }
+
+    /// Parameters of a collection as written to its structure file
+    async fn parameters(&self, name: &str) -> Result<Parameters> {
+        let endpoint: String = Router::extension(name, API::Collection(api::Collection::Properties));
+        let response: FlatResponse<Parameters> = self.client.get(endpoint).await?;
+        Ok(response.result)
+    }
+
+    async fn collection_names(&self, options: &DumpOptions) -> Result<Vec<String>> {
+        if let Some(collections) = &options.collections {
+            return Ok(collections.clone());
+        }
+        let collections: Vec<Information> = Collection::new(self.client.clone(), String::new()).read().await?;
+        Ok(collections.into_iter()
+            .filter(|c| options.include_system || !c.is_system)
+            .map(|c| c.name)
+            .collect())
+    }
+
+    /// Structures of the dump, sorted by collection name.
+    async fn structures(directory: &Path, options: &RestoreOptions) -> Result<Vec<Structure>> {
+        let mut structures: Vec<Structure> = vec![];
+        let mut entries = fs::read_dir(directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_name().to_string_lossy().ends_with(".structure.json") {
+                continue;
+            }
+            let structure: Structure = serde_json::from_slice(&fs::read(entry.path()).await?)?;
+            let selected: bool = match &options.collections {
+                Some(collections) => collections.contains(&structure.parameters.name),
+                None => true,
+            };
+            if selected {
+                structures.push(structure);
+            }
+        }
+        structures.sort_by(|a, b| a.parameters.name.cmp(&b.parameters.name));
+        Ok(structures)
+    }
+
+    /// Data file of a collection and the encoding of its content.
+    async fn data_file(directory: &Path, name: &str) -> Result<Option<(PathBuf, Option<Encoding>)>> {
+        let mut entries = fs::read_dir(directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_name: String = entry.file_name().to_string_lossy().into_owned();
+            let (stem, encoding) = match file_name.strip_suffix(".data.json.gz") {
+                Some(stem) => (stem, Some(Encoding::Gzip)),
+                None => match file_name.strip_suffix(".data.json") {
+                    Some(stem) => (stem, None),
+                    None => continue,
+                },
+            };
+            if is_data_of(stem, name) {
+                return Ok(Some((entry.path(), encoding)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Matches `<name>` and the hashed `<name>_<md5>` written by arangodump.
+fn is_data_of(stem: &str, name: &str) -> bool {
+    match stem.strip_prefix(name) {
+        Some("") => true,
+        Some(rest) => match rest.strip_prefix('_') {
+            Some(hash) => hash.len() == 32 && hash.chars().all(|c| c.is_ascii_hexdigit()),
+            None => false,
+        },
+        None => false,
+    }
+}
+
+fn new_collection(parameters: Parameters) -> NewCollection {
+    let mut new_collection: NewCollection = NewCollection::new(parameters.name, parameters.cache_enabled, parameters.computed_values);
+    new_collection.r#type = parameters.r#type;
+    new_collection.is_system = parameters.is_system;
+    new_collection.wait_for_sync = parameters.wait_for_sync;
+    if let Some(write_concern) = parameters.write_concern {
+        new_collection.write_concern = write_concern;
+    }
+    if let Some(replication_factor) = parameters.replication_factor {
+        new_collection.replication_factor = replication_factor;
+    }
+    new_collection.number_of_shards = parameters.number_of_shards;
+    new_collection.shard_keys = parameters.shard_keys;
+    new_collection.sharding_strategy = parameters.sharding_strategy;
+    new_collection.key_options = parameters.key_options;
+    new_collection
+}
+
+fn new_index(collection_name: &str, idx: Idx) -> NewIdx {
+    NewIdx {
+        collection: collection_name.to_owned(),
+        name: idx.name,
+        r#type: idx.r#type,
+        fields: idx.fields,
+        in_background: false,
+        unique: idx.unique,
+        min_length: idx.min_length,
+        geo_json: idx.geo_json,
+        stored_values: idx.stored_values,
+        sparse: idx.sparse,
+        deduplicate: idx.deduplicate,
+        estimates: idx.estimates,
+        cache_enabled: idx.cache_enabled,
+        expires_after: idx.expires_after,
+        field_value_types: idx.field_value_types
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_data_of, DumpInfo, Structure};
+
+    #[test]
+    fn test_data_file_names() {
+        assert!(is_data_of("users", "users"));
+        assert!(is_data_of("users_0d8a5a2b4c3e1f6a7b8c9d0e1f2a3b4c", "users"));
+
+        assert!(!is_data_of("users_archive", "users"));
+        assert!(!is_data_of("users_archive_0d8a5a2b4c3e1f6a7b8c9d0e1f2a3b4c", "users"));
+        assert!(!is_data_of("user", "users"));
+    }
+
+    #[test]
+    fn test_arangodump_structure() {
+        let structure: Structure = serde_json::from_str(include_str!("../tests/fixtures/arangodump/dump_fixture.structure.json")).unwrap();
+        assert_eq!(structure.parameters.name, "dump_fixture");
+        assert_eq!(structure.parameters.r#type, 2);
+        assert_eq!(structure.parameters.replication_factor, Some(1));
+        assert_eq!(structure.parameters.key_options.unwrap().last_value, 21447);
+        assert_eq!(structure.indexes[0].fields, vec!["email"]);
+
+        let info: DumpInfo = serde_json::from_str(include_str!("../tests/fixtures/arangodump/dump.json")).unwrap();
+        assert_eq!(info.database, "_system");
+        assert!(!info.use_envelope);
+
+        // cluster dumps omit lastValue, satellite collections have no replication count
+        let structure: Structure = serde_json::from_str(r#"{
+            "indexes": [],
+            "parameters": {
+                "name": "satellite",
+                "type": 2,
+                "keyOptions": { "allowUserKeys": true, "type": "traditional" },
+                "replicationFactor": "satellite"
+            }
+        }"#).unwrap();
+        assert_eq!(structure.parameters.replication_factor, None);
+        assert_eq!(structure.parameters.key_options.unwrap().last_value, 0);
+        assert!(structure.parameters.computed_values.is_none());
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+use crate::{Result, Client, AsKey, DocumentRouter, Router, WriteMeta, WriteOptions, keys::collection_name};
+
+/*
+    Schemaless document access.
+    Documents are plain JSON and the collection is passed with every call,
+    for collections that are not modelled by a record type.
+*/
+
+/// Outcome of a DynDocument write
+#[derive(Deserialize, Debug)]
+pub struct DynWriteResult {
+    /// None for silent writes
+    #[serde(flatten)]
+    pub meta: Option<WriteMeta>,
+
+    /// Present when WriteOptions::return_new is set
+    pub new: Option<Value>,
+
+    /// Present when WriteOptions::return_old is set
+    pub old: Option<Value>
+}
+
+/// Document API over serde_json::Value
+/// Collection names are validated before they become part of a route.
+/// ```ignore
+/// let documents: DynDocument = Arango::new(&config);
+/// let user: Value = documents.read("users", "1").await?;
+/// ```
+pub struct DynDocument {
+    pub client: Client
+}
+
+impl DynDocument {
+    /// Insert a document into collection
+    pub async fn insert<D: Serialize + Sync + Send>(&self, collection: &str, document: &D, options: &WriteOptions) -> Result<DynWriteResult> {
+        let collection: &str = collection_name(collection)?;
+        self.client.post(Router::base_with_params(collection, options.params())?, document).await
+    }
+
+    /// Read a document by key
+    pub async fn read(&self, collection: &str, key: &(impl AsKey + ?Sized)) -> Result<Value> {
+        let collection: &str = collection_name(collection)?;
+        self.client.get(Router::key(collection, key.key_in(collection)?)).await
+    }
+
+    /// Read multiple documents by key
+    /// Missing documents are returned as error objects in place.
+    pub async fn read_many<S: Serialize + Sync + Send>(&self, collection: &str, keys: &[S]) -> Result<Vec<Value>> {
+        let collection: &str = collection_name(collection)?;
+        self.client.put(Router::read_many(collection), &keys).await
+    }
+
+    /// Merge the attributes of patch into a document
+    pub async fn update<D: Serialize + Sync + Send>(&self, collection: &str, key: &(impl AsKey + ?Sized), patch: &D, options: &WriteOptions) -> Result<DynWriteResult> {
+        let collection: &str = collection_name(collection)?;
+        self.client.patch(Router::key_with_params(collection, key.key_in(collection)?, options.params())?, patch).await
+    }
+
+    /// Replace a document
+    pub async fn replace<D: Serialize + Sync + Send>(&self, collection: &str, key: &(impl AsKey + ?Sized), document: &D, options: &WriteOptions) -> Result<DynWriteResult> {
+        let collection: &str = collection_name(collection)?;
+        self.client.put(Router::key_with_params(collection, key.key_in(collection)?, options.params())?, document).await
+    }
+
+    /// Delete a document
+    pub async fn delete(&self, collection: &str, key: &(impl AsKey + ?Sized), options: &WriteOptions) -> Result<DynWriteResult> {
+        let collection: &str = collection_name(collection)?;
+        self.client.delete(Router::key_with_params(collection, key.key_in(collection)?, options.params())?).await
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::{Result, Metadata, Timestamps, Doc, Edge, Document, DocumentId, Direction, EdgeQueryParams, EdgesRouter, Router, name};
+
+/*
+    Edges of a single vertex through _api/edges.
+    Cheaper than a traversal when only the direct neighbourhood is needed.
+*/
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeStats {
+    pub scanned_index: u64,
+    pub filtered: u64
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(bound = "R: DeserializeOwned + Sync + Send, M: DeserializeOwned")]
+pub struct EdgesResponse<R, M = Timestamps>
+where
+    R: Sized + Sync + Send {
+    pub edges: Vec<Doc<Edge<R>, M>>,
+    pub stats: EdgeStats
+}
+
+impl<M: Metadata> Document<M> {
+    /// Edges of the collection of R starting or ending at vertex
+    /// ```ignore
+    /// let outbound: EdgesResponse<Follows> = document.edges(&user.keys.id, Direction::Out).await?;
+    /// ```
+    pub async fn edges<R: Serialize + DeserializeOwned + Sync + Send>(&self, vertex: &DocumentId, direction: Direction) -> Result<EdgesResponse<R, M>> {
+        let params: EdgeQueryParams = EdgeQueryParams {
+            vertex: vertex.to_string(),
+            direction: match direction {
+                Direction::Any => None,
+                direction => Some(direction),
+            }
+        };
+        self.client.get(Router::base_with_params(&name::<R>(), params)?).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Direction, EdgeQueryParams, EdgesRouter, Router};
+
+    #[test]
+    fn test_edges_route() {
+        let params: EdgeQueryParams = EdgeQueryParams { vertex: "users/1".to_owned(), direction: Some(Direction::Out) };
+        assert_eq!(Router::base_with_params("follows", params).unwrap(), "_api/edges/follows?vertex=users%2F1&direction=out");
+
+        let params: EdgeQueryParams = EdgeQueryParams { vertex: "users/1".to_owned(), direction: None };
+        assert_eq!(Router::base_with_params("follows", params).unwrap(), "_api/edges/follows?vertex=users%2F1");
+    }
+}
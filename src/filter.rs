@@ -0,0 +1,314 @@
+use serde_json::{Map, Value};
+
+use crate::BoundCursorRequest;
+
+/*
+    AQL filters built from field references.
+    Neither attribute names nor values end up in the query text,
+    both are passed as bind parameters.
+*/
+
+/// Reference to a document attribute
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldRef(Vec<String>);
+
+impl FieldRef {
+    /// Attribute path, nested attributes are separated by dots: `address.city`
+    pub fn new(path: &str) -> Self {
+        Self(path.split('.').map(str::to_owned).collect())
+    }
+
+    /// Single attribute, dots are part of its name
+    pub fn attribute(name: impl Into<String>) -> Self {
+        Self(vec![name.into()])
+    }
+
+    pub fn key() -> Self {
+        Self::attribute("_key")
+    }
+}
+
+impl From<&str> for FieldRef {
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<String> for FieldRef {
+    fn from(path: String) -> Self {
+        Self::new(&path)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    /// value is an array
+    In,
+    /// value is an array
+    NotIn,
+    /// `%` and `_` are wildcards
+    Like
+}
+
+impl Comparison {
+    fn operator(self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Lte => "<=",
+            Self::Gt => ">",
+            Self::Gte => ">=",
+            Self::In => "IN",
+            Self::NotIn => "NOT IN",
+            Self::Like => "LIKE",
+        }
+    }
+}
+
+/// Condition on the attributes of a document
+/// ```ignore
+/// let filter: Filter = Filter::eq("address.city", "Berlin").and(Filter::gte("age", 18));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    Compare {
+        field: FieldRef,
+        comparison: Comparison,
+        value: Value
+    },
+    /// true when empty
+    And(Vec<Filter>),
+    /// false when empty
+    Or(Vec<Filter>),
+    Not(Box<Filter>)
+}
+
+impl Filter {
+    pub fn compare(field: impl Into<FieldRef>, comparison: Comparison, value: impl Into<Value>) -> Self {
+        Self::Compare { field: field.into(), comparison, value: value.into() }
+    }
+
+    pub fn eq(field: impl Into<FieldRef>, value: impl Into<Value>) -> Self {
+        Self::compare(field, Comparison::Eq, value)
+    }
+
+    pub fn ne(field: impl Into<FieldRef>, value: impl Into<Value>) -> Self {
+        Self::compare(field, Comparison::Ne, value)
+    }
+
+    pub fn lt(field: impl Into<FieldRef>, value: impl Into<Value>) -> Self {
+        Self::compare(field, Comparison::Lt, value)
+    }
+
+    pub fn lte(field: impl Into<FieldRef>, value: impl Into<Value>) -> Self {
+        Self::compare(field, Comparison::Lte, value)
+    }
+
+    pub fn gt(field: impl Into<FieldRef>, value: impl Into<Value>) -> Self {
+        Self::compare(field, Comparison::Gt, value)
+    }
+
+    pub fn gte(field: impl Into<FieldRef>, value: impl Into<Value>) -> Self {
+        Self::compare(field, Comparison::Gte, value)
+    }
+
+    pub fn one_of<V: Into<Value>>(field: impl Into<FieldRef>, values: Vec<V>) -> Self {
+        Self::compare(field, Comparison::In, values)
+    }
+
+    pub fn like(field: impl Into<FieldRef>, pattern: &str) -> Self {
+        Self::compare(field, Comparison::Like, pattern)
+    }
+
+    pub fn and(self, other: Filter) -> Self {
+        match self {
+            Self::And(mut filters) => {
+                filters.push(other);
+                Self::And(filters)
+            },
+            filter => Self::And(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: Filter) -> Self {
+        match self {
+            Self::Or(mut filters) => {
+                filters.push(other);
+                Self::Or(filters)
+            },
+            filter => Self::Or(vec![filter, other]),
+        }
+    }
+
+    pub fn negate(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+
+    /// AQL condition on the document variable
+    pub(crate) fn render(&self, variable: &str, bindings: &mut Bindings) -> String {
+        match self {
+            Self::Compare { field, comparison, value } => {
+                let attribute: String = bindings.attribute(variable, field);
+                let value: String = bindings.value(value.clone());
+                format!("{} {} {}", attribute, comparison.operator(), value)
+            },
+            Self::And(filters) => join(filters, " && ", "true", variable, bindings),
+            Self::Or(filters) => join(filters, " || ", "false", variable, bindings),
+            Self::Not(filter) => format!("!({})", filter.render(variable, bindings)),
+        }
+    }
+}
+
+fn join(filters: &[Filter], separator: &str, empty: &str, variable: &str, bindings: &mut Bindings) -> String {
+    if filters.is_empty() {
+        return empty.to_owned();
+    }
+    let conditions: Vec<String> = filters.iter()
+        .map(|filter| format!("({})", filter.render(variable, bindings)))
+        .collect();
+    conditions.join(separator)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Order {
+    #[default]
+    Asc,
+    Desc
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sort {
+    pub field: FieldRef,
+    pub order: Order
+}
+
+impl Sort {
+    pub fn asc(field: impl Into<FieldRef>) -> Self {
+        Self { field: field.into(), order: Order::Asc }
+    }
+
+    pub fn desc(field: impl Into<FieldRef>) -> Self {
+        Self { field: field.into(), order: Order::Desc }
+    }
+
+    /// SORT clause, empty without sorts
+    pub(crate) fn render(sorts: &[Sort], variable: &str, bindings: &mut Bindings) -> String {
+        if sorts.is_empty() {
+            return String::new();
+        }
+        let sorts: Vec<String> = sorts.iter()
+            .map(|sort| {
+                let order: &str = match sort.order {
+                    Order::Asc => "ASC",
+                    Order::Desc => "DESC",
+                };
+                format!("{} {}", bindings.attribute(variable, &sort.field), order)
+            })
+            .collect();
+        format!("SORT {}", sorts.join(", "))
+    }
+}
+
+/// Bind parameters of a generated query
+#[derive(Default, Debug)]
+pub(crate) struct Bindings(Map<String, Value>);
+
+impl Bindings {
+    /// `@@collection`
+    pub(crate) fn collection(&mut self, name: &str) -> &'static str {
+        self.0.insert("@collection".to_owned(), Value::from(name));
+        "@@collection"
+    }
+
+    /// `@@edges`, the edge collection of a traversal
+    pub(crate) fn edges(&mut self, name: &str) -> &'static str {
+        self.0.insert("@edges".to_owned(), Value::from(name));
+        "@@edges"
+    }
+
+    pub(crate) fn value(&mut self, value: Value) -> String {
+        let name: String = format!("value{}", self.0.len());
+        self.0.insert(name.clone(), value);
+        format!("@{}", name)
+    }
+
+    /// `variable.@attribute0.@attribute1`, one bind parameter per path segment
+    pub(crate) fn attribute(&mut self, variable: &str, field: &FieldRef) -> String {
+        let mut attribute: String = variable.to_owned();
+        for segment in &field.0 {
+            let name: String = format!("attribute{}", self.0.len());
+            self.0.insert(name.clone(), Value::from(segment.as_str()));
+            attribute.push_str(".@");
+            attribute.push_str(&name);
+        }
+        attribute
+    }
+
+    pub(crate) fn into_inner(self) -> Map<String, Value> {
+        self.0
+    }
+
+    /// Cursor request of query with these bindings
+    pub(crate) fn request(self, query: String) -> BoundCursorRequest<Map<String, Value>> {
+        let mut request: BoundCursorRequest<Map<String, Value>> = BoundCursorRequest::new(self.0);
+        request.query = query;
+        request.count = false;
+        request
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use super::{Bindings, FieldRef, Filter, Sort};
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = Filter::eq("address.city", "Berlin")
+            .and(Filter::gte("age", 18))
+            .and(Filter::one_of("role", vec!["admin", "owner"]).or(Filter::like("name", "a%")).negate());
+
+        let mut bindings: Bindings = Bindings::default();
+        let collection: &str = bindings.collection("user");
+        let condition: String = filter.render("d", &mut bindings);
+        assert_eq!(collection, "@@collection");
+        assert_eq!(condition, "(d.@attribute1.@attribute2 == @value3) && (d.@attribute4 >= @value5) \
+            && (!((d.@attribute6 IN @value7) || (d.@attribute8 LIKE @value9)))");
+
+        let bound: Value = Value::Object(bindings.into_inner());
+        assert_eq!(bound, json!({
+            "@collection": "user",
+            "attribute1": "address", "attribute2": "city", "value3": "Berlin",
+            "attribute4": "age", "value5": 18,
+            "attribute6": "role", "value7": ["admin", "owner"],
+            "attribute8": "name", "value9": "a%"
+        }));
+    }
+
+    #[test]
+    fn test_injection() {
+        // names and values are never part of the query
+        let filter: Filter = Filter::eq(FieldRef::attribute("x == 1 || true"), "\" || true");
+        let mut bindings: Bindings = Bindings::default();
+        assert_eq!(filter.render("d", &mut bindings), "d.@attribute0 == @value1");
+
+        assert_eq!(Filter::And(vec![]).render("d", &mut bindings), "true");
+        assert_eq!(Filter::Or(vec![]).render("d", &mut bindings), "false");
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut bindings: Bindings = Bindings::default();
+        assert_eq!(Sort::render(&[], "d", &mut bindings), "");
+        assert_eq!(Sort::render(&[Sort::desc("created_on"), Sort::asc(FieldRef::key())], "d", &mut bindings),
+            "SORT d.@attribute0 DESC, d.@attribute1 ASC");
+    }
+}
@@ -0,0 +1,259 @@
+use std::any::{type_name, TypeId};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use log::warn;
+
+use crate::{Result, Document, WriteMeta, WriteResult};
+
+/*
+    Lifecycle hooks of Document writes.
+    Hooks are registered per record type, a before hook failing aborts the write.
+    Records are handed to before hooks as a copy, the copy is written instead of the record.
+    After hooks run once the write is committed, their errors are logged and never fail the call.
+*/
+
+/// Callbacks around the writes of records R.
+/// before_update runs for update and replace, before_patch for patch and Document::save.
+/// An error of an after hook is logged, the write is reported as succeeded.
+/// ```ignore
+/// struct Audit;
+///
+/// impl Hooks<SampleData> for Audit {
+///     fn before_insert(&self, record: &mut SampleData) -> Result<()> {
+///         record.body = record.body.trim().to_owned();
+///         Ok(())
+///     }
+/// }
+///
+/// let document: Document = Document::from(client).with_hooks(Audit);
+/// ```
+pub trait Hooks<R>: Send + Sync {
+    fn before_insert(&self, _record: &mut R) -> Result<()> {
+        Ok(())
+    }
+
+    fn after_insert(&self, _written: &Written<R>) -> Result<()> {
+        Ok(())
+    }
+
+    fn before_update(&self, _key: &str, _record: &mut R) -> Result<()> {
+        Ok(())
+    }
+
+    /// patch holds the changed attributes, it is sent as is
+    fn before_patch(&self, _key: &str, _patch: &Value) -> Result<()> {
+        Ok(())
+    }
+
+    fn after_update(&self, _key: &str, _written: &Written<R>) -> Result<()> {
+        Ok(())
+    }
+
+    fn before_delete(&self, _key: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn after_delete(&self, _key: &str, _written: &Written<R>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A committed write, as handed to after hooks
+#[derive(Debug)]
+pub struct Written<'a, R> {
+    /// Record as written, None for patches and deletes
+    pub record: Option<&'a R>,
+
+    /// None for silent writes
+    pub meta: Option<&'a WriteMeta>,
+
+    /// Present when the write returned the new document
+    pub new: Option<&'a R>,
+
+    /// Present when the write returned the old document
+    pub old: Option<&'a R>
+}
+
+impl<'a, R: Sync + Send> Written<'a, R> {
+    pub(crate) fn of<M>(record: Option<&'a R>, result: &'a WriteResult<R, M>) -> Self {
+        Self {
+            record,
+            meta: result.meta.as_ref(),
+            new: result.new.as_ref().map(|doc| &doc.record),
+            old: result.old.as_ref().map(|doc| &doc.record)
+        }
+    }
+}
+
+/// Hooks of R as stored by Document, by the TypeId of R
+pub(crate) struct Registered<R> {
+    hooks: Box<dyn Hooks<R>>,
+    /// Copy of a record handed to before hooks
+    copy: fn(&R) -> R
+}
+
+/// Record as passed by the caller, or the copy changed by a before hook
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum Hooked<'a, R> {
+    Record(&'a R),
+    Changed(R)
+}
+
+impl<R> Hooked<'_, R> {
+    pub(crate) fn record(&self) -> &R {
+        match self {
+            Hooked::Record(record) => record,
+            Hooked::Changed(record) => record,
+        }
+    }
+}
+
+impl<M> Document<M> {
+    /// Run hooks around the writes of records R, replaces hooks registered for R before
+    pub fn with_hooks<R, H>(mut self, hooks: H) -> Self
+    where
+        R: Clone + Send + Sync + 'static,
+        H: Hooks<R> + 'static {
+        let registered: Registered<R> = Registered { hooks: Box::new(hooks), copy: R::clone };
+        self.hooks.insert(TypeId::of::<R>(), Box::new(registered));
+        self
+    }
+
+    pub(crate) fn hooks_of<R: 'static>(&self) -> Option<&Registered<R>> {
+        self.hooks.get(&TypeId::of::<R>()).and_then(|hooks| hooks.downcast_ref::<Registered<R>>())
+    }
+
+    pub(crate) fn run_before_insert<'a, R: 'static>(&self, record: &'a R) -> Result<Hooked<'a, R>> {
+        match self.hooks_of::<R>() {
+            Some(registered) => {
+                let mut copy: R = (registered.copy)(record);
+                registered.hooks.before_insert(&mut copy)?;
+                Ok(Hooked::Changed(copy))
+            },
+            None => Ok(Hooked::Record(record)),
+        }
+    }
+
+    pub(crate) fn run_after_insert<R: 'static>(&self, written: &Written<R>) {
+        if let Some(registered) = self.hooks_of::<R>() {
+            logged::<R>("after_insert", registered.hooks.after_insert(written));
+        }
+    }
+
+    pub(crate) fn run_before_update<'a, R: 'static>(&self, key: &str, record: &'a R) -> Result<Hooked<'a, R>> {
+        match self.hooks_of::<R>() {
+            Some(registered) => {
+                let mut copy: R = (registered.copy)(record);
+                registered.hooks.before_update(key, &mut copy)?;
+                Ok(Hooked::Changed(copy))
+            },
+            None => Ok(Hooked::Record(record)),
+        }
+    }
+
+    pub(crate) fn run_before_patch<R: 'static>(&self, key: &str, patch: &impl Serialize) -> Result<()> {
+        match self.hooks_of::<R>() {
+            Some(registered) => registered.hooks.before_patch(key, &serde_json::to_value(patch)?),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn run_after_update<R: 'static>(&self, key: &str, written: &Written<R>) {
+        if let Some(registered) = self.hooks_of::<R>() {
+            logged::<R>("after_update", registered.hooks.after_update(key, written));
+        }
+    }
+
+    pub(crate) fn run_before_delete<R: 'static>(&self, key: &str) -> Result<()> {
+        self.hooks_of::<R>().map_or(Ok(()), |registered| registered.hooks.before_delete(key))
+    }
+
+    pub(crate) fn run_after_delete<R: 'static>(&self, key: &str, written: &Written<R>) {
+        if let Some(registered) = self.hooks_of::<R>() {
+            logged::<R>("after_delete", registered.hooks.after_delete(key, written));
+        }
+    }
+}
+
+/// The write is committed, an after hook can only report its failure
+fn logged<R>(hook: &str, result: Result<()>) {
+    if let Err(error) = result {
+        warn!("{} hook of {} failed: {}", hook, type_name::<R>(), error);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Serialize, Deserialize};
+    use serde_json::{json, Value};
+
+    use crate::{Result, Client, ClientError, Config, Document, Hooks};
+
+    use super::{Hooked, Written};
+
+    #[derive(Serialize, Deserialize, Clone)]
+    struct Sample {
+        body: String
+    }
+
+    struct Trim;
+
+    impl Hooks<Sample> for Trim {
+        fn before_insert(&self, record: &mut Sample) -> Result<()> {
+            record.body = record.body.trim().to_owned();
+            Ok(())
+        }
+
+        fn before_patch(&self, _key: &str, patch: &Value) -> Result<()> {
+            match patch.get("body") {
+                Some(Value::String(body)) if body.trim() != body => Err(ClientError::validation_error("untrimmed body".to_owned())),
+                _ => Ok(()),
+            }
+        }
+
+        fn after_insert(&self, _written: &Written<Sample>) -> Result<()> {
+            Err(ClientError::validation_error("not audited".to_owned()))
+        }
+
+        fn before_delete(&self, key: &str) -> Result<()> {
+            Err(ClientError::validation_error(format!("{} is protected", key)))
+        }
+    }
+
+    fn document() -> Document {
+        let config: Config = Config {
+            host: "http://localhost:8529".to_owned(),
+            database: "_system".to_owned(),
+            user: "root".to_owned(),
+            password: "".to_owned()
+        };
+        Document::from(Client::new(&config))
+    }
+
+    #[test]
+    fn test_hooks() {
+        let record: Sample = Sample { body: " body ".to_owned() };
+
+        // without hooks the record is sent as is
+        let document: Document = document();
+        assert!(matches!(document.run_before_insert(&record).unwrap(), Hooked::Record(_)));
+
+        let document: Document = document.with_hooks(Trim);
+        let hooked: Hooked<Sample> = document.run_before_insert(&record).unwrap();
+        assert_eq!(hooked.record().body, "body");
+        assert_eq!(serde_json::to_value(&hooked).unwrap(), json!({ "body": "body" }));
+        assert_eq!(record.body, " body ");
+
+        assert!(document.run_before_delete::<Sample>("1").is_err());
+        assert!(document.run_before_delete::<String>("1").is_ok());
+
+        assert!(document.run_before_patch::<Sample>("1", &json!({ "body": " body" })).is_err());
+        assert!(document.run_before_patch::<Sample>("1", &json!({ "other": " body" })).is_ok());
+
+        // the failing after hook does not fail the committed write
+        document.run_after_insert(&Written { record: Some(hooked.record()), meta: None, new: None, old: None });
+    }
+}
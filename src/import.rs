@@ -0,0 +1,122 @@
+use std::io;
+
+use futures_util::{Stream, StreamExt, stream};
+use hyper::{Body, body::Bytes};
+use serde::{Serialize, Deserialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{Result, Client, Router, ImportRouter, ImportQueryParams, ImportType, Encoding, name};
+
+/*
+    Bulk import through /_api/import.
+    Bodies are streamed to the server as they are read or produced,
+    nothing is buffered on the client beyond a single chunk.
+*/
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Summary of an import
+#[derive(Deserialize, Debug)]
+pub struct ImportResponse {
+    pub created: u64,
+    pub errors: u64,
+    pub empty: u64,
+    pub updated: u64,
+    pub ignored: u64,
+
+    /// Reason of every failed line, requires ImportQueryParams::details
+    #[serde(default)]
+    pub details: Vec<String>
+}
+
+pub struct Import {
+    pub client: Client
+}
+
+impl Import {
+    /// Import the JSON read from `reader` into a collection.
+    /// The layout of the input is given by ImportQueryParams::type, e.g. a JSONL file for ImportType::Documents.
+    pub async fn import_reader<A: AsyncRead + Unpin + Send + 'static>(&self, collection_name: &str, reader: A, params: &ImportQueryParams) -> Result<ImportResponse> {
+        self.import_encoded(collection_name, reader, None, params).await
+    }
+
+    /// Import the content of `reader` compressed with `encoding`, the server inflates it.
+    pub(crate) async fn import_encoded<A: AsyncRead + Unpin + Send + 'static>(&self, collection_name: &str, reader: A, encoding: Option<Encoding>, params: &ImportQueryParams) -> Result<ImportResponse> {
+        let body: Body = Body::wrap_stream(read_chunks(reader));
+        self.client.post_body(Router::base_with_params(collection_name, params)?, body, encoding).await
+    }
+
+    /// Import a stream of records into the collection of R.
+    /// Every record is sent as a line of JSONL, the type of the params is ignored.
+    pub async fn import_stream<R, S>(&self, records: S, params: &ImportQueryParams) -> Result<ImportResponse>
+    where
+        R: Serialize + Send + 'static,
+        S: Stream<Item = R> + Send + 'static {
+        let mut params: ImportQueryParams = params.clone();
+        params.r#type = ImportType::Documents;
+
+        let lines = records.map(|record| {
+            let mut line: Vec<u8> = serde_json::to_vec(&record)?;
+            line.push(b'\n');
+            Ok::<Bytes, serde_json::Error>(Bytes::from(line))
+        });
+        self.client.post_body(Router::base_with_params(&name::<R>(), &params)?, Body::wrap_stream(lines), None).await
+    }
+}
+
+/// Chunks of the reader until it is exhausted or fails.
+fn read_chunks<A: AsyncRead + Unpin + Send + 'static>(reader: A) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+    stream::unfold(Some((reader, vec![0u8; CHUNK_SIZE])), |state| async move {
+        let (mut reader, mut buffer) = state?;
+        match reader.read(&mut buffer).await {
+            Ok(0) => None,
+            Ok(n) => Some((Ok(Bytes::copy_from_slice(&buffer[..n])), Some((reader, buffer)))),
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use futures_util::StreamExt;
+
+    use crate::{ImportQueryParams, OnDuplicate, ImportType, Router, ImportRouter};
+
+    use super::read_chunks;
+
+    #[test]
+    fn test_import_route() {
+        let params: ImportQueryParams = ImportQueryParams {
+            on_duplicate: Some(OnDuplicate::Update),
+            from_prefix: Some("users".to_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Router::base_with_params("sample", &params).unwrap(),
+            "_api/import?collection=sample&type=documents&onDuplicate=update&fromPrefix=users&details=true"
+        );
+
+        let params: ImportQueryParams = ImportQueryParams {
+            r#type: ImportType::Auto,
+            complete: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Router::base_with_params("sample", &params).unwrap(),
+            "_api/import?collection=sample&type=auto&complete=true&details=true"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_chunks() {
+        let data: Vec<u8> = b"{\"body\": \"line\"}\n".repeat(10_000);
+
+        let chunks: Vec<_> = read_chunks(std::io::Cursor::new(data.clone())).collect().await;
+        assert!(chunks.len() > 1);
+
+        let read: Vec<u8> = chunks.into_iter().flat_map(|chunk| chunk.unwrap().to_vec()).collect();
+        assert_eq!(read, data);
+    }
+}
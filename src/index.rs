@@ -69,7 +69,7 @@ impl Default for NewIdx {
 }
 
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Idx{
     pub fields: Vec<String>,
@@ -78,16 +78,27 @@ pub struct Idx{
     #[serde(rename = "type")]
     pub r#type: String,
 
-    pub selectivity_estimate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selectivity_estimate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub unique: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub min_length: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub geo_json: Option<bool>,
-    pub stored_values: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_values: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sparse: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub deduplicate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub estimates: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_after: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     /// "double" is only supported value
     pub field_value_types: Option<String>
 }
@@ -0,0 +1,327 @@
+use std::{fmt, ops::Deref, str::FromStr};
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer, de};
+
+use crate::{ClientError, Result, name};
+
+/*
+    Typed document identifiers.
+    A DocumentId carries its collection, Document methods refuse an id
+    of another collection instead of looking up the key in the wrong one.
+*/
+
+/// Characters allowed in a key besides ASCII letters and digits
+const KEY_PUNCTUATION: &str = "_-:.@()+,=;$!*'%";
+const MAX_KEY_LENGTH: usize = 254;
+const MAX_COLLECTION_LENGTH: usize = 256;
+
+/// The `_key` of a document, validated when deserialized
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct DocumentKey(String);
+
+impl DocumentKey {
+    /// Fails with a validation error unless key only uses the legal key characters
+    pub fn new(key: impl Into<String>) -> Result<Self> {
+        let key: String = key.into();
+        let legal: bool = !key.is_empty()
+            && key.len() <= MAX_KEY_LENGTH
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || KEY_PUNCTUATION.contains(c));
+        if !legal {
+            return Err(ClientError::validation_error(format!("invalid document key {:?}", key)));
+        }
+        Ok(Self(key))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+/// The `_rev` of a document
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct DocumentRevision(String);
+
+impl DocumentRevision {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for DocumentRevision {
+    fn from(rev: String) -> Self {
+        Self(rev)
+    }
+}
+
+impl From<&str> for DocumentRevision {
+    fn from(rev: &str) -> Self {
+        Self(rev.to_owned())
+    }
+}
+
+/// The `_id` of a document, serialized as `collection/key`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DocumentId {
+    pub collection: String,
+    pub key: DocumentKey
+}
+
+/// Fails with a validation error if collection is not a legal collection name
+pub(crate) fn collection_name(collection: &str) -> Result<&str> {
+    let legal: bool = collection.len() <= MAX_COLLECTION_LENGTH
+        && collection.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && collection.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !legal {
+        return Err(ClientError::validation_error(format!("invalid collection name {:?}", collection)));
+    }
+    Ok(collection)
+}
+
+impl DocumentId {
+    /// Fails with a validation error if collection is not a legal collection name
+    pub fn new(collection: impl Into<String>, key: DocumentKey) -> Result<Self> {
+        let collection: String = collection.into();
+        collection_name(&collection)?;
+        Ok(Self { collection, key })
+    }
+
+    /// Id of the document with key in the collection of R
+    pub fn of<R>(key: DocumentKey) -> Self {
+        Self { collection: name::<R>(), key }
+    }
+
+    /// Parse `collection/key`
+    pub fn parse(id: &str) -> Result<Self> {
+        match id.split_once('/') {
+            Some((collection, key)) => Self::new(collection, DocumentKey::new(key)?),
+            None => Err(ClientError::validation_error(format!("invalid document id {:?}", id))),
+        }
+    }
+}
+
+impl fmt::Display for DocumentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.collection, self.key)
+    }
+}
+
+impl FromStr for DocumentId {
+    type Err = ClientError;
+
+    fn from_str(id: &str) -> Result<Self> {
+        Self::parse(id)
+    }
+}
+
+impl Serialize for DocumentId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DocumentId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let id: String = String::deserialize(deserializer)?;
+        Self::parse(&id).map_err(de::Error::custom)
+    }
+}
+
+impl PartialEq<str> for DocumentId {
+    fn eq(&self, other: &str) -> bool {
+        other.split_once('/').is_some_and(|(collection, key)| self.collection == collection && self.key == key)
+    }
+}
+
+impl PartialEq<&str> for DocumentId {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+/// Deref, Display and string comparisons of the string newtypes
+macro_rules! string_newtype {
+    ($name:ident) => {
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+    };
+}
+
+string_newtype!(DocumentKey);
+string_newtype!(DocumentRevision);
+
+impl TryFrom<String> for DocumentKey {
+    type Error = ClientError;
+
+    fn try_from(key: String) -> Result<Self> {
+        Self::new(key)
+    }
+}
+
+impl From<DocumentKey> for String {
+    fn from(key: DocumentKey) -> Self {
+        key.0
+    }
+}
+
+impl FromStr for DocumentKey {
+    type Err = ClientError;
+
+    fn from_str(key: &str) -> Result<Self> {
+        Self::new(key)
+    }
+}
+
+/// A document reference accepted by Document methods.
+/// Plain strings are sent as given, a DocumentId must belong to the collection.
+pub trait AsKey {
+    /// The key within collection
+    fn key_in(&self, collection: &str) -> Result<&str>;
+}
+
+impl AsKey for str {
+    fn key_in(&self, _collection: &str) -> Result<&str> {
+        Ok(self)
+    }
+}
+
+impl AsKey for String {
+    fn key_in(&self, _collection: &str) -> Result<&str> {
+        Ok(self)
+    }
+}
+
+impl AsKey for DocumentKey {
+    fn key_in(&self, _collection: &str) -> Result<&str> {
+        Ok(self.as_str())
+    }
+}
+
+impl AsKey for DocumentId {
+    fn key_in(&self, collection: &str) -> Result<&str> {
+        if self.collection != collection {
+            return Err(ClientError::validation_error(format!("{} is not a document of {}", self, collection)));
+        }
+        Ok(self.key.as_str())
+    }
+}
+
+/// A document referenced by its `_id`, e.g. a vertex of an edge
+pub trait AsId {
+    fn as_id(&self) -> &DocumentId;
+}
+
+impl AsId for DocumentId {
+    fn as_id(&self) -> &DocumentId {
+        self
+    }
+}
+
+impl<T: AsId + ?Sized> AsId for &T {
+    fn as_id(&self) -> &DocumentId {
+        (**self).as_id()
+    }
+}
+
+impl<T: AsKey + ?Sized> AsKey for &T {
+    fn key_in(&self, collection: &str) -> Result<&str> {
+        (**self).key_in(collection)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{AsKey, DocumentId, DocumentKey, DocumentRevision};
+
+    use super::collection_name;
+
+    #[test]
+    fn test_document_key() {
+        assert!(DocumentKey::new("a-Z_0:9.@()+,=;$!*'%").is_ok());
+        assert!(DocumentKey::new("").is_err());
+        assert!(DocumentKey::new("with/slash").is_err());
+        assert!(DocumentKey::new("with space").is_err());
+        assert!(DocumentKey::new("x".repeat(255)).is_err());
+
+        // deserialized keys are validated as well
+        assert_eq!(serde_json::from_str::<DocumentKey>(r#""a-1""#).unwrap(), "a-1");
+        assert!(serde_json::from_str::<DocumentKey>(r#""with/slash""#).is_err());
+    }
+
+    #[test]
+    fn test_collection_name() {
+        assert_eq!(collection_name("_system-users_2").unwrap(), "_system-users_2");
+        assert!(collection_name("").is_err());
+        assert!(collection_name("users/1").is_err());
+        assert!(collection_name("users?x=1").is_err());
+        assert!(collection_name("../_api").is_err());
+    }
+
+    #[test]
+    fn test_document_id() {
+        let id: DocumentId = DocumentId::parse("sample/1").unwrap();
+        assert_eq!(id.collection, "sample");
+        assert_eq!(id.key, "1");
+        assert_eq!(id, "sample/1");
+
+        assert!(DocumentId::parse("sample").is_err());
+        assert!(DocumentId::parse("1sample/1").is_err());
+        assert!(DocumentId::parse("sample/1/2").is_err());
+
+        // transparent serialization
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""sample/1""#);
+        assert_eq!(serde_json::from_str::<DocumentId>(r#""sample/1""#).unwrap(), id);
+        assert!(serde_json::from_str::<DocumentId>(r#""sample""#).is_err());
+        assert_eq!(serde_json::to_string(&id.key).unwrap(), r#""1""#);
+        assert_eq!(serde_json::from_str::<DocumentRevision>(r#""_gyOmZey---""#).unwrap(), "_gyOmZey---");
+
+        // keys of other collections are refused
+        assert_eq!(id.key_in("sample").unwrap(), "1");
+        assert!(id.key_in("other").is_err());
+        assert_eq!("1".key_in("other").unwrap(), "1");
+    }
+}
@@ -8,8 +8,28 @@ mod params;
 mod responses;
 mod query;
 mod model;
+mod stream;
+mod request;
+mod import;
+mod dump;
+mod patch;
+mod tracked;
+mod cache;
+mod edges;
+mod keys;
+mod dynamic;
+mod validate;
+mod metadata;
+mod hooks;
+mod filter;
+mod repository;
+mod relational;
+pub mod vpack;
 
-use std::{time::SystemTime, any::type_name};
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+use std::{any::type_name, time::Duration};
 use convert_case::{Case, Casing};
 
 pub use client::*;
@@ -22,14 +42,31 @@ pub use query::*;
 pub use model::*;
 pub use document::*;
 pub use collection::*;
+pub use stream::*;
+pub use request::*;
+pub use import::*;
+pub use dump::*;
+pub use patch::*;
+pub use tracked::*;
+pub use edges::*;
+pub use keys::*;
+pub use dynamic::*;
+pub use metadata::*;
+pub use hooks::{Hooks, Written};
+pub use filter::{FieldRef, Comparison, Filter, Order, Sort};
+pub use repository::*;
+pub use relational::*;
+pub use cache::CacheOptions;
 
 
-pub fn get_current_timestamp() -> u64 {
-    return SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs() as u64;
+/// Current time in unit, compare with Timestamps in the same unit
+pub fn get_current_timestamp(unit: TimeUnit) -> u64 {
+    return unit.now();
 }
 
-pub  fn get_default_expiration() -> u64 {
-    return get_current_timestamp() + (86400 * 30 * 6); // 6 months
+/// Expiration in unit, TTL indexes expect TimeUnit::Seconds
+pub  fn get_default_expiration(unit: TimeUnit) -> u64 {
+    return get_current_timestamp(unit) + unit.from_duration(Duration::from_secs(86400 * 30 * 6)); // 6 months
 }
 
 fn extract_name(name: String) -> String {
@@ -78,7 +115,13 @@ impl Arango for Database {
     }
 }
 
-impl Arango for Document {
+impl<M> Arango for Document<M> {
+    fn new(config: &Config) -> Self {
+        Self::from(Client::new(config))
+    }
+}
+
+impl Arango for DynDocument {
     fn new(config: &Config) -> Self {
         Self {
             client: Client::new(config)
@@ -100,4 +143,20 @@ impl Arango for Index {
             client: Client::new(config)
         }
     }
+}
+
+impl Arango for Import {
+    fn new(config: &Config) -> Self {
+        Self {
+            client: Client::new(config)
+        }
+    }
+}
+
+impl Arango for Dump {
+    fn new(config: &Config) -> Self {
+        Self {
+            client: Client::new(config)
+        }
+    }
 }
\ No newline at end of file
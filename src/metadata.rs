@@ -0,0 +1,188 @@
+use std::{marker::PhantomData, time::{Duration, SystemTime}};
+
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use crate::ComputedValue;
+
+/*
+    Metadata stored next to the record of a document.
+    The policy is the second parameter of Doc and is chosen per Document handle,
+    Timestamps carry their unit so the computed values and the decoded times agree.
+*/
+
+/// Unit of stored timestamps
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TimeUnit {
+    Seconds,
+    /// Unit of DATE_NOW()
+    #[default]
+    Milliseconds
+}
+
+impl TimeUnit {
+    /// Current time since the unix epoch
+    pub fn now(self) -> u64 {
+        self.from_duration(SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default())
+    }
+
+    pub fn from_duration(self, duration: Duration) -> u64 {
+        match self {
+            Self::Seconds => duration.as_secs(),
+            Self::Milliseconds => duration.as_millis() as u64,
+        }
+    }
+
+    pub fn to_duration(self, value: u64) -> Duration {
+        match self {
+            Self::Seconds => Duration::from_secs(value),
+            Self::Milliseconds => Duration::from_millis(value),
+        }
+    }
+
+    /// AQL expression of the current time
+    pub fn expression(self) -> &'static str {
+        match self {
+            Self::Seconds => "RETURN FLOOR(DATE_NOW() / 1000)",
+            Self::Milliseconds => "RETURN DATE_NOW()",
+        }
+    }
+}
+
+/// Metadata policy of Doc
+pub trait Metadata: Serialize + DeserializeOwned + Sync + Send {
+    /// Computed values maintaining the metadata, empty if it is written by the application
+    fn computed_values() -> Vec<ComputedValue> {
+        vec![]
+    }
+}
+
+/// Unit of a Timestamps policy
+pub trait Unit: Sync + Send + 'static {
+    const UNIT: TimeUnit;
+}
+
+/// Timestamps in milliseconds, the unit of DATE_NOW()
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Milliseconds;
+
+impl Unit for Milliseconds {
+    const UNIT: TimeUnit = TimeUnit::Milliseconds;
+}
+
+/// Timestamps in seconds, the unit of TTL indexes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Seconds;
+
+impl Unit for Seconds {
+    const UNIT: TimeUnit = TimeUnit::Seconds;
+}
+
+/// Creation and modification time in the unit U, computed by the collection
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timestamps<U = Milliseconds> {
+    pub modified_on: u64,
+    pub created_on: u64,
+    #[serde(skip)]
+    unit: PhantomData<U>
+}
+
+impl<U: Unit> Timestamps<U> {
+    pub fn new(modified_on: u64, created_on: u64) -> Self {
+        Self { modified_on, created_on, unit: PhantomData }
+    }
+
+    /// Creation time since the unix epoch
+    pub fn created(&self) -> Duration {
+        U::UNIT.to_duration(self.created_on)
+    }
+
+    /// Modification time since the unix epoch
+    pub fn modified(&self) -> Duration {
+        U::UNIT.to_duration(self.modified_on)
+    }
+}
+
+impl<U: Unit> Metadata for Timestamps<U> {
+    fn computed_values() -> Vec<ComputedValue> {
+        let expression: &str = U::UNIT.expression();
+        vec![
+            ComputedValue{
+                name: "created_on".to_owned(),
+                expression: expression.to_owned(),
+                compute_on: vec![
+                    "insert".to_owned(),
+                    "replace".to_owned()
+                ],
+                overwrite: true,
+                keep_null: false,
+                fail_on_warning: true,
+            },
+            ComputedValue{
+                name: "modified_on".to_owned(),
+                expression: expression.to_owned(),
+                compute_on: vec![
+                    "insert".to_owned(),
+                    "update".to_owned(),
+                    "replace".to_owned(),
+                ],
+                overwrite: true,
+                keep_null: false,
+                fail_on_warning: true,
+            }
+        ]
+    }
+}
+
+/// No metadata, for collections without computed values
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct NoMetadata {}
+
+impl Metadata for NoMetadata {}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use serde::{Serialize, Deserialize};
+
+    use crate::{Doc, Metadata, NoMetadata, Seconds, TimeUnit, Timestamps};
+
+    #[derive(Serialize, Deserialize)]
+    struct Sample {
+        body: String
+    }
+
+    #[test]
+    fn test_time_unit() {
+        let duration: Duration = Duration::from_millis(1_500);
+        assert_eq!(TimeUnit::Seconds.from_duration(duration), 1);
+        assert_eq!(TimeUnit::Milliseconds.from_duration(duration), 1_500);
+        assert_eq!(TimeUnit::Seconds.to_duration(2), Duration::from_secs(2));
+        assert!(TimeUnit::Milliseconds.now() / 1000 >= TimeUnit::Seconds.now() - 1);
+    }
+
+    #[test]
+    fn test_metadata_policies() {
+        let data: &str = r#"{ "_id": "sample/1", "_key": "1", "_rev": "1", "body": "body" }"#;
+        assert!(serde_json::from_str::<Doc<Sample, Timestamps>>(data).is_err());
+
+        let doc: Doc<Sample, NoMetadata> = serde_json::from_str(data).unwrap();
+        assert_eq!(doc.record.body, "body");
+        assert_eq!(serde_json::to_value(&doc).unwrap()["body"], "body");
+
+        let data: &str = r#"{ "_id": "sample/1", "_key": "1", "_rev": "1", "body": "body", "modified_on": 2, "created_on": 1 }"#;
+        let doc: Doc<Sample> = serde_json::from_str(data).unwrap();
+        assert_eq!(doc.metadata, Timestamps::new(2, 1));
+        assert_eq!(doc.metadata.created(), Duration::from_millis(1));
+
+        let doc: Doc<Sample, Timestamps<Seconds>> = serde_json::from_str(data).unwrap();
+        assert_eq!(doc.metadata.modified(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_computed_units() {
+        assert_eq!(<Timestamps>::computed_values()[0].expression, "RETURN DATE_NOW()");
+        assert_eq!(Timestamps::<Seconds>::computed_values()[1].expression, "RETURN FLOOR(DATE_NOW() / 1000)");
+        assert!(NoMetadata::computed_values().is_empty());
+    }
+}
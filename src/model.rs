@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use crate::ClientError;
+use crate::{ClientError, DocumentId, DocumentKey, DocumentRevision};
 
 pub trait Validator: Serialize + Send {
     fn validate(&self) -> Result<(), ClientError>;
@@ -11,19 +11,19 @@ pub struct Empty{}
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct ArangoKeys {
     #[serde(rename="_id")]
-    pub id: String,
+    pub id: DocumentId,
     
     #[serde(rename="_key")]
-    pub key: String,           
+    pub key: DocumentKey,           
 
     #[serde(rename="_rev")]
-    pub rev: String,
+    pub rev: DocumentRevision,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct ArangoEdgeKeys {
     #[serde(rename="_to")]
-    pub to: String,             
+    pub to: DocumentId,             
     #[serde(rename="_from")]
-    pub from: String,           
+    pub from: DocumentId,           
 }
@@ -1,6 +1,19 @@
 use serde::Serialize;
 
 
+/// Behavior of an insert when a document with the same _key exists
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OverwriteMode {
+    /// Keep the existing document
+    Ignore,
+    /// Replace the existing document
+    Replace,
+    /// Merge into the existing document, see keep_null and merge_objects
+    Update,
+    /// Fail with a unique constraint violation (default)
+    Conflict
+}
 
 #[derive(Serialize)]
 pub struct DocumentQueryParams{
@@ -23,7 +36,7 @@ pub struct DocumentQueryParams{
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "overwriteMode")]
-    pub overwrite_method: Option<String>, // "ignore", "replace", "update", "conflict"    
+    pub overwrite_mode: Option<OverwriteMode>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "keepNull")]
@@ -46,14 +59,13 @@ impl Default for DocumentQueryParams {
     fn default() -> Self {
         Self { 
             // default behavior enforces new document to be returned
-            // DocumentResponse is tightly coupled to these settings.
-            // Not all of them are currently supported, stick with the default for now.
+            // Document writes build their params from WriteOptions instead.
             return_new: Some(true),
             return_old: None,
             silent: None,
             wait_for_sync: true,
             overwrite: None,
-            overwrite_method: None,
+            overwrite_mode: None,
             keep_null: None,
             merge_objects: None,
             refill_index_cache: None,
@@ -62,10 +74,23 @@ impl Default for DocumentQueryParams {
     }
 }
 
+/// Direction of the edges of a vertex
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Edges with the vertex as _to
+    In,
+    /// Edges with the vertex as _from
+    Out,
+    /// Both, the direction parameter is omitted
+    Any
+}
+
 #[derive(Serialize)]
 pub struct EdgeQueryParams{
     pub vertex: String,
-    pub direction: Option<String>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>
 }
 
 impl Default for EdgeQueryParams {
@@ -75,4 +100,76 @@ impl Default for EdgeQueryParams {
             direction: None,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Layout of the body of an import
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportType {
+    /// One JSON document per line (JSONL)
+    Documents,
+    /// A single JSON array of documents
+    Array,
+    /// Detected by the server
+    Auto
+}
+
+/// Action taken when an imported document violates a unique constraint
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OnDuplicate {
+    Error,
+    Update,
+    Replace,
+    Ignore
+}
+
+#[derive(Serialize, Clone)]
+pub struct ImportQueryParams{
+    #[serde(rename = "type")]
+    pub r#type: ImportType,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "onDuplicate")]
+    pub on_duplicate: Option<OnDuplicate>,
+
+    /// Abort the whole import if any document fails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complete: Option<bool>,
+
+    /// Truncate the collection before importing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overwrite: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "waitForSync")]
+    pub wait_for_sync: Option<bool>,
+
+    /// Prefix for _from values of edges, e.g. "users"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "fromPrefix")]
+    pub from_prefix: Option<String>,
+
+    /// Prefix for _to values of edges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "toPrefix")]
+    pub to_prefix: Option<String>,
+
+    /// Report the reason of every failed line
+    pub details: bool
+}
+
+impl Default for ImportQueryParams {
+    fn default() -> Self {
+        Self {
+            r#type: ImportType::Documents,
+            on_duplicate: None,
+            complete: None,
+            overwrite: None,
+            wait_for_sync: None,
+            from_prefix: None,
+            to_prefix: None,
+            details: true
+        }
+    }
+}
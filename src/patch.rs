@@ -0,0 +1,208 @@
+use serde::{Serialize, Serializer, Deserializer, de::{self, Visitor, DeserializeOwned}, forward_to_deserialize_any};
+use serde_json::{Map, Value};
+
+/*
+    Partial updates for Document::patch.
+    Only the attributes present in a patch are sent,
+    concurrent writers touching other attributes are not overwritten.
+*/
+
+/// A partial update of the record R
+pub trait Patch<R>: Serialize + Send + Sync {}
+
+impl<R> Patch<R> for Value {}
+
+impl<R> Patch<R> for Map<String, Value> {}
+
+/// Attribute of a patch
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Field<T> {
+    /// Not sent, the stored value is kept
+    #[default]
+    Unchanged,
+    /// Sent as null, removes the attribute when WriteOptions::keep_null is Some(false)
+    Remove,
+    Set(T)
+}
+
+impl<T> Field<T> {
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Self::Unchanged)
+    }
+}
+
+impl<T> From<T> for Field<T> {
+    fn from(value: T) -> Self {
+        Self::Set(value)
+    }
+}
+
+impl<T: Serialize> Serialize for Field<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Set(value) => value.serialize(serializer),
+            _ => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Records the attribute names of a struct, see record_attributes
+struct AttributeNames<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for AttributeNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], _visitor: V) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(de::Error::custom("attribute names only"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Serialized attribute names of a struct R, renames included.
+/// None for records without a fixed set of attributes (maps, flattened structs).
+fn record_attributes<R: DeserializeOwned>() -> Option<&'static [&'static str]> {
+    let mut attributes: Option<&'static [&'static str]> = None;
+    let _ = R::deserialize(AttributeNames(&mut attributes));
+    attributes
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+
+    /// Fails unless attribute is an attribute of R
+    pub fn check_attribute<R: serde::de::DeserializeOwned, E: serde::ser::Error>(attribute: &str) -> Result<(), E> {
+        match super::record_attributes::<R>() {
+            Some(attributes) if !attributes.contains(&attribute) => Err(E::custom(format!(
+                "{} is not an attribute of {}, rename it in the patch", attribute, std::any::type_name::<R>()
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Declare a patch type for a record, every field becomes a `Field`.
+/// Fields are sent under their name, `as "name"` sets the attribute of a renamed record field.
+/// Serializing fails when a field is not an attribute of the record.
+/// ```ignore
+/// blandango::patch! {
+///     pub struct SampleDataPatch for SampleData {
+///         pub body: String,
+///         pub tags: Vec<String>,
+///         pub created_by as "createdBy": String
+///     }
+/// }
+///
+/// let patch: SampleDataPatch = SampleDataPatch { body: "patched".to_owned().into(), ..Default::default() };
+/// document.patch(&key, &patch, &WriteOptions::default()).await?;
+/// ```
+#[macro_export]
+macro_rules! patch {
+    (@attribute $field:ident as $attribute:literal) => {
+        $attribute
+    };
+    (@attribute $field:ident) => {
+        stringify!($field)
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident for $record:ty {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident $(as $attribute:literal)? : $ty:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Default)]
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $crate::Field<$ty>,
+            )*
+        }
+
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S: $crate::__private::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                use $crate::__private::serde::ser::SerializeMap;
+
+                $(
+                    $crate::__private::check_attribute::<$record, S::Error>($crate::patch!(@attribute $field $(as $attribute)?))?;
+                )*
+                let mut map = serializer.serialize_map(None)?;
+                $(
+                    if !self.$field.is_unchanged() {
+                        map.serialize_entry($crate::patch!(@attribute $field $(as $attribute)?), &self.$field)?;
+                    }
+                )*
+                map.end()
+            }
+        }
+
+        impl $crate::Patch<$record> for $name {}
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use crate::Field;
+
+    // only its attribute names are used
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Sample {
+        body: String,
+        count: u32,
+        tags: Vec<String>,
+        created_by: String
+    }
+
+    crate::patch! {
+        /// Patch of Sample
+        pub struct SamplePatch for Sample {
+            pub body: String,
+            pub count: u32,
+            pub tags: Vec<String>,
+            pub created_by as "createdBy": String
+        }
+    }
+
+    crate::patch! {
+        pub struct UnrenamedPatch for Sample {
+            pub created_by: String
+        }
+    }
+
+    #[test]
+    fn test_patch_serialize() {
+        let patch: SamplePatch = SamplePatch::default();
+        assert_eq!(serde_json::to_value(&patch).unwrap(), json!({}));
+
+        let patch: SamplePatch = SamplePatch {
+            body: "patched".to_owned().into(),
+            tags: Field::Remove,
+            created_by: "me".to_owned().into(),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(&patch).unwrap(), json!({ "body": "patched", "tags": null, "createdBy": "me" }));
+
+        // the record stores createdBy
+        let patch: UnrenamedPatch = UnrenamedPatch { created_by: "me".to_owned().into() };
+        assert!(serde_json::to_value(&patch).is_err());
+        assert_eq!(super::record_attributes::<Value>(), None);
+    }
+}
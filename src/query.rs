@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize, de::DeserializeOwned};
 use serde_json::{Map, Value};
-use crate::{Client, Result, Router, QueryRouter, IdResponse, Empty, Response};
+use futures_util::{Stream, stream};
+use crate::{Client, Result, Router, QueryRouter, IdResponse, Empty, Response, ArrayStream};
 
 
 pub struct Query{
@@ -34,6 +35,30 @@ impl Query {
         Ok(response)
     }
 
+    /// Create Bound Cursor
+    /// Results are deserialized one at a time, following the cursor across batches.
+    pub async fn bound_cursor_stream<B: Serialize + Send + Sync, R: DeserializeOwned + Send + Sync>(&self, request: &BoundCursorRequest<B>) -> Result<CursorStream<R>>{
+        let batch: ArrayStream<R> = self.client.post_stream(Router::cursor(&request.id), request, Some("result")).await?;
+        Ok(CursorStream { client: self.client.clone(), batch })
+    }
+
+    /// Every result of a bound cursor, across batches
+    pub(crate) async fn collect<B: Serialize + Send + Sync, R: DeserializeOwned + Send + Sync>(&self, request: &BoundCursorRequest<B>) -> Result<Vec<R>> {
+        let mut stream: CursorStream<R> = self.bound_cursor_stream(request).await?;
+        let mut results: Vec<R> = vec![];
+        while let Some(result) = stream.next().await? {
+            results.push(result);
+        }
+        Ok(results)
+    }
+
+    /// Create Cursor
+    /// Results are deserialized one at a time, following the cursor across batches.
+    pub async fn cursor_stream<R: DeserializeOwned + Send + Sync>(&self, request: &CursorRequest) -> Result<CursorStream<R>>{
+        let batch: ArrayStream<R> = self.client.post_stream(Router::cursor(&request.id), request, Some("result")).await?;
+        Ok(CursorStream { client: self.client.clone(), batch })
+    }
+
     /// Delete Cursor
     pub async fn delete_cursor(&self, cursor_id: String) -> Result<IdResponse>{
         let response: IdResponse = self.client.delete(Router::cursor(&Some(cursor_id))).await?;
@@ -196,42 +221,60 @@ pub struct Optimizer {
     pub rules: String
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub full_count: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fill_block_cache: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_number_of_plans: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_nodes_per_callstack: Option<u32>,
     /// default = 10
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_warning_count: Option<u8>, 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fail_on_warning: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
     /// Experimental features | Default = 128MB
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub spill_over_threshold_memory_usage: Option<u64>,
     /// Default = 5,000,000
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub spill_over_threshold_num_rows: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub optimizer: Option<Optimizer>,
     /// Provides the extra.profile, extra.nodes, and extra.plan attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub profile: Option<u8>,
 
     /// Enterprise Edition
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub satelite_sync_wait: Option<f32>,
 
     /// Default = 0.0
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_runtime: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_transaction_size: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub intermediate_commit_size: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub intermediate_commit_count: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub skip_inaccessible_collections: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_dirty_reads: Option<bool>
 }
 
@@ -399,6 +442,65 @@ pub struct CursorResponse<T> {
     pub cached: bool,
 }
 
+/// Results of a cursor, fetched batch by batch and deserialized one at a time.
+pub struct CursorStream<R> {
+    client: Client,
+    batch: ArrayStream<R>
+}
+
+impl<R: DeserializeOwned> CursorStream<R> {
+    /// Next result, None once the cursor is exhausted.
+    pub async fn next(&mut self) -> Result<Option<R>> {
+        loop {
+            if let Some(item) = self.batch.next().await? {
+                return Ok(Some(item));
+            }
+
+            // the batch has been consumed, its envelope tells if the cursor has more
+            let envelope: &Map<String, Value> = self.batch.envelope().await?;
+            let has_more: bool = envelope.get("hasMore").and_then(Value::as_bool).unwrap_or(false);
+            let id: Option<String> = envelope.get("id").and_then(Value::as_str).map(|id| id.to_owned());
+
+            match id {
+                Some(id) if has_more => {
+                    self.batch = self.client.put_stream(Router::cursor(&Some(id)), &Empty{}, Some("result")).await?;
+                },
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    /// Delete the cursor before it is exhausted.
+    /// The rest of the current batch is skipped to learn the cursor id.
+    pub async fn close(mut self) -> Result<()> {
+        let envelope: &Map<String, Value> = self.batch.finish().await?;
+        let has_more: bool = envelope.get("hasMore").and_then(Value::as_bool).unwrap_or(false);
+        let id: Option<String> = envelope.get("id").and_then(Value::as_str).map(|id| id.to_owned());
+
+        match id {
+            Some(id) if has_more => {
+                let query: Query = Query { client: self.client };
+                query.delete_cursor(id).await?;
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Convert into a `futures` Stream of results.
+    pub fn into_stream(self) -> impl Stream<Item = Result<R>> {
+        stream::unfold(Some(self), |state| async move {
+            let mut cursor: CursorStream<R> = state?;
+            match cursor.next().await {
+                Ok(Some(item)) => Some((Ok(item), Some(cursor))),
+                Ok(None) => None,
+                // end the stream after the first error
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RunningQuery {
@@ -1,107 +1,199 @@
-// use async_trait::async_trait;
-// use serde::Serialize;
-
-// use crate::{document::*, Result, QueryBound, QueryResponse, Client, DatabaseError};
-
-
-// const SAMPLE: &str = 
-// r#"
-// FOR loc in location
-// FILTER loc._key == @location_key
-//     FOR equip in equipment
-//     FILTER equip.location_id == loc._id &&
-//         (@active == null || equip.active == @active) && 
-//         (@location_key == null || loc._key == @location_key) && 
-//         (@equipment_key == null || equip._key == @equipment_key)
-//     FOR template IN equipment_template
-//     FILTER template._id == equip.template_id
-//     RETURN MERGE(equip, {template: template})
-// "#;
-
-// fn filter_key(key: String) -> String {
-//     format!("FILTER d._key == \"{}\"", key)
-// }
-
-// /*
-//     A Provider is a Model that provides relational
-//     abilities between another model.
-// */
-// #[async_trait]
-// pub trait Provider<T: 'static + Document>
-//     where Self:Document {
-
-//     // collection header: `FOR x in collection`
-//     fn root() -> String {
-//         format!("FOR d in {0}", Self::collection_name())
-//     }
-
-//     fn collection_id() -> String {
-//         format!("{0}_id", Self::collection_name())
-//     }
-
-//     // collection header: `FOR x in collection`
-//     async fn add<M: Serialize + Send + Sync>(client: &Client, key: String, model: M) -> Result<T> {
-//         let q: String = format!("
-//             {0}
-//             {1}
-//             INSERT MERGE(@model, {{ {2}_id: d._id }}) INTO {3}
-//             RETURN MERGE(NEW, {{ {2}: d }})
-//             ", 
-//             Self::root(), 
-//             filter_key(key),
-//             Self::collection_name(),
-//             <T as Document>::collection_name(),
-//         ).to_owned();
-
-//         let query: QueryBound<ModelParams<M>> = QueryBound::new(q.clone(), ModelParams{model});
-
-//         let mut results: QueryResponse<Vec<T>> = T::bounded_query(client, &query).await?;
-//         if results.result.len() > 0 {
-//             return Ok(results.result.pop().unwrap());
-//         }
-//         println!("query: {}", &q);
-//         return Err(DatabaseError::insertion_error("failed to create related document".to_owned()));
-//     }
-
-//     async fn edit<M: Serialize + Send + Sync>(client: &Client, parent_key: String, child_key: String, model: M) -> Result<T> {
-//         let q: String = format!("
-//             {0}
-//             {1}
-//             FOR c IN {3}
-//             FILTER c.{2}_id == d._id && c._key == \"{4}\"
-//             UPDATE c._key WITH MERGE(@model, {{ {2}_id: d._id }}) IN {3}
-//             RETURN MERGE(NEW, {{ {2}: d }})
-//             ", 
-//             Self::root(), 
-//             filter_key(parent_key),
-//             Self::collection_name(),
-//             <T as Document>::collection_name(),
-//             child_key
-//         ).to_owned();
-
-//         let query: QueryBound<ModelParams<M>> = QueryBound::new(q, ModelParams{model});
-
-//         let mut results: QueryResponse<Vec<T>> = T::bounded_query(client, &query).await?;
-//         if results.result.len() > 0 {
-//             return Ok(results.result.pop().unwrap());
-//         }
-//         return Err(DatabaseError::insertion_error("failed to edit related document".to_owned()));
-//     }       
-
-//     // async fn find(client: &Client) -> Result<T> {
-//     //     let q: String = format!("            
-//     //     FOR co in company
-//     //     FILTER co._key == @company_key
-//     //     FOR loc in location
-//     //     FILTER loc.company_id == co._id &&
-//     //         (@active == null || loc.active == @active) && 
-//     //         (@location_key == null || loc._key == @location_key)
-//     //     RETURN MERGE(loc, {company: co})");
-//     // }
-// }
-
-
-// #[derive(Serialize, Clone)]
-// pub struct ModelParams<T>{
-//     pub model: T
-// }
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::{
+    Result, ClientError, AsKey, Doc, DocumentId, DocumentKey, Metadata, Timestamps, Repository,
+    WriteMeta, WriteOptions, WriteResult, Written, FieldRef, Filter, filter::Bindings, name
+};
+
+/*
+    Parent/child relations between record types.
+    One-to-many relations keep the `_id` of the parent in an attribute of the child,
+    many-to-many relations link parents and children through an edge collection.
+*/
+
+/// How children reference their parent
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// Children hold the `_id` of their parent in the attribute
+    Foreign(String),
+    /// Edges from parent to child in the edge collection
+    Edges(String)
+}
+
+impl Relation {
+    /// Edges in the collection of E
+    pub fn edges<E>() -> Self {
+        Self::Edges(name::<E>())
+    }
+}
+
+/// A record type providing children C
+/// ```ignore
+/// // posts hold a user_id
+/// impl Provider<Post> for User {}
+///
+/// // tags and posts are linked by the tagged edge collection
+/// impl Provider<Post> for Tag {
+///     fn relation() -> Relation {
+///         Relation::edges::<Tagged>()
+///     }
+/// }
+///
+/// let posts: Repository<Post> = Arango::new(&config);
+/// let post: WithParent<Post, User> = posts.add_child(&user.keys.key, &record).await?;
+/// let written: Vec<Doc<Post>> = posts.children::<User>(&user.keys.key).await?;
+/// ```
+pub trait Provider<C>: Sized {
+    /// A foreign `{parent}_id` attribute by default
+    fn relation() -> Relation {
+        Relation::Foreign(format!("{}_id", name::<Self>()))
+    }
+}
+
+/// A child document and its parent
+#[derive(Deserialize, Debug)]
+#[serde(bound = "C: DeserializeOwned + Sync + Send, P: DeserializeOwned + Sync + Send, M: DeserializeOwned")]
+pub struct WithParent<C, P, M = Timestamps>
+where
+    C: Sized + Sync + Send,
+    P: Sized + Sync + Send {
+    #[serde(flatten)]
+    pub doc: Doc<C, M>,
+
+    /// None for orphans
+    pub parent: Option<Doc<P, M>>
+}
+
+/// AQL array of the parents of the child variable
+fn parents_of<P>(relation: &Relation, child: &str, bindings: &mut Bindings) -> String {
+    match relation {
+        Relation::Foreign(field) => format!(
+            "(FOR p IN [DOCUMENT({})] FILTER p != null && IS_SAME_COLLECTION({}, p) RETURN p)",
+            bindings.attribute(child, &FieldRef::attribute(field.as_str())),
+            bindings.value(Value::from(name::<P>()))
+        ),
+        Relation::Edges(edges) => format!(
+            "(FOR p IN 1..1 INBOUND {} {} FILTER IS_SAME_COLLECTION({}, p) RETURN p)",
+            child,
+            bindings.edges(edges),
+            bindings.value(Value::from(name::<P>()))
+        ),
+    }
+}
+
+impl<C, M> Repository<C, M>
+where
+    C: Serialize + DeserializeOwned + Sync + Send + 'static,
+    M: Metadata {
+    /// Insert a child of parent, it is returned with its parent.
+    /// The child and the edge of a many-to-many relation are inserted by a single query,
+    /// neither is written if the other fails.
+    pub async fn add_child<P>(&self, parent: &(impl AsKey + ?Sized), child: &C) -> Result<WithParent<C, P, M>>
+    where
+        P: Provider<C> + Serialize + DeserializeOwned + Sync + Send {
+        let parent: Doc<P, M> = self.document.read(parent).await?;
+        let doc: Doc<C, M> = match P::relation() {
+            Relation::Foreign(field) => {
+                let foreign: Map<String, Value> = Map::from_iter([(field, Value::from(parent.keys.id.to_string()))]);
+                let result: WriteResult<C, M> = self.document.insert_extended(child, foreign, WriteOptions::default().params()).await?;
+                result.new.ok_or_else(|| ClientError::insertion_error(format!("insert into {} was not returned", name::<C>())))?
+            },
+            Relation::Edges(edges) => {
+                self.document.edge_collection(&edges).await?;
+                let mut bindings: Bindings = Bindings::default();
+                let query: String = format!(
+                    "LET c = FIRST(INSERT {} INTO {} RETURN NEW) INSERT {{ _from: {}, _to: c._id }} INTO {} RETURN c",
+                    bindings.value(serde_json::to_value(self.document.run_before_insert(child)?)?),
+                    bindings.collection(&name::<C>()),
+                    bindings.value(Value::from(parent.keys.id.to_string())),
+                    bindings.edges(&edges)
+                );
+                let doc: Doc<C, M> = self.first(query, bindings).await?;
+                let meta: WriteMeta = WriteMeta { id: doc.keys.id.clone(), key: doc.keys.key.clone(), rev: doc.keys.rev.clone(), old_rev: None };
+                self.document.invalidate::<C>(&doc.keys.key);
+                self.document.run_after_insert(&Written { record: Some(&doc.record), meta: Some(&meta), new: Some(&doc.record), old: None });
+                doc
+            },
+        };
+        Ok(WithParent { doc, parent: Some(parent) })
+    }
+
+    /// Children of parent
+    pub async fn children<P: Provider<C>>(&self, parent: &(impl AsKey + ?Sized)) -> Result<Vec<Doc<C, M>>> {
+        let parent: DocumentId = DocumentId::of::<P>(DocumentKey::new(parent.key_in(&name::<P>())?)?);
+        match P::relation() {
+            Relation::Foreign(field) => self.find(&Filter::eq(FieldRef::attribute(field), parent.to_string())).await,
+            Relation::Edges(edges) => {
+                let mut bindings: Bindings = Bindings::default();
+                let query: String = format!(
+                    "FOR c IN 1..1 OUTBOUND {} {} FILTER IS_SAME_COLLECTION({}, c) RETURN c",
+                    bindings.value(Value::from(parent.to_string())),
+                    bindings.edges(&edges),
+                    bindings.value(Value::from(name::<C>()))
+                );
+                self.all(query, bindings).await
+            },
+        }
+    }
+
+    /// Parents of a child, at most one for Relation::Foreign
+    pub async fn parents<P>(&self, child: &(impl AsKey + ?Sized)) -> Result<Vec<Doc<P, M>>>
+    where
+        P: Provider<C> + DeserializeOwned + Sync + Send {
+        let key: &str = child.key_in(&name::<C>())?;
+        let mut bindings: Bindings = Bindings::default();
+        let query: String = format!(
+            "FOR c IN {} FILTER c._key == {} RETURN {}",
+            bindings.collection(&name::<C>()),
+            bindings.value(Value::from(key)),
+            parents_of::<P>(&P::relation(), "c", &mut bindings)
+        );
+        let mut parents: Vec<Vec<Doc<P, M>>> = self.all(query, bindings).await?;
+        Ok(parents.pop().unwrap_or_default())
+    }
+
+    /// Parent of a child, the first one of a many-to-many relation
+    pub async fn parent<P>(&self, child: &(impl AsKey + ?Sized)) -> Result<Option<Doc<P, M>>>
+    where
+        P: Provider<C> + DeserializeOwned + Sync + Send {
+        Ok(self.parents::<P>(child).await?.into_iter().next())
+    }
+
+    /// Children matching filter, loaded with their parent in the same query
+    pub async fn with_parent<P>(&self, filter: &Filter) -> Result<Vec<WithParent<C, P, M>>>
+    where
+        P: Provider<C> + DeserializeOwned + Sync + Send {
+        let mut bindings: Bindings = Bindings::default();
+        let query: String = format!(
+            "FOR c IN {} FILTER {} RETURN MERGE(c, {{ parent: FIRST({}) }})",
+            bindings.collection(&name::<C>()),
+            filter.render("c", &mut bindings),
+            parents_of::<P>(&P::relation(), "c", &mut bindings)
+        );
+        self.all(query, bindings).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{filter::Bindings, Relation};
+
+    use super::parents_of;
+
+    struct Tag;
+    struct Tagged;
+
+    #[test]
+    fn test_parents_of() {
+        let mut bindings: Bindings = Bindings::default();
+        assert_eq!(parents_of::<Tag>(&Relation::Foreign("tag_id".to_owned()), "c", &mut bindings),
+            "(FOR p IN [DOCUMENT(c.@attribute0)] FILTER p != null && IS_SAME_COLLECTION(@value1, p) RETURN p)");
+        assert_eq!(serde_json::Value::Object(bindings.into_inner()), serde_json::json!({ "attribute0": "tag_id", "value1": "tag" }));
+
+        let mut bindings: Bindings = Bindings::default();
+        assert_eq!(parents_of::<Tag>(&Relation::edges::<Tagged>(), "c", &mut bindings),
+            "(FOR p IN 1..1 INBOUND c @@edges FILTER IS_SAME_COLLECTION(@value1, p) RETURN p)");
+        assert_eq!(serde_json::Value::Object(bindings.into_inner()), serde_json::json!({ "@edges": "tagged", "value1": "tag" }));
+    }
+}
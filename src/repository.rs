@@ -0,0 +1,174 @@
+use std::marker::PhantomData;
+
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{
+    Result, ClientError, Arango, Config, AsKey, CacheOptions, Doc, Document, DocumentId, DocumentKey, Hooks,
+    Metadata, Timestamps, Query, CursorResponse, CursorStream, WriteOptions, WriteResult,
+    FieldRef, Filter, Sort, filter::Bindings, name
+};
+
+/*
+    Typed access to the collection of one record type.
+    Queries are generated from Filter and Sort, everything variable is bound.
+*/
+
+/// A page of documents and the number of documents in the collection
+#[derive(Deserialize, Debug)]
+#[serde(bound = "R: DeserializeOwned + Sync + Send, M: DeserializeOwned")]
+pub struct Page<R, M = Timestamps>
+where
+    R: Sized + Sync + Send {
+    pub items: Vec<Doc<R, M>>,
+    pub total: u64
+}
+
+/// Document and Query bound to the collection of R
+/// ```ignore
+/// let users: Repository<User> = Arango::new(&config);
+/// let admins: Vec<Doc<User>> = users.find_by("role", "admin").await?;
+/// let newest: Page<User> = users.page(0, 20, &[Sort::desc("created_on")]).await?;
+/// ```
+pub struct Repository<R, M = Timestamps> {
+    pub document: Document<M>,
+    pub query: Query,
+    record: PhantomData<fn() -> (R, M)>
+}
+
+impl<R, M> From<Document<M>> for Repository<R, M> {
+    fn from(document: Document<M>) -> Self {
+        Self {
+            query: Query { client: document.client.clone() },
+            document,
+            record: PhantomData
+        }
+    }
+}
+
+impl<R, M> Arango for Repository<R, M> {
+    fn new(config: &Config) -> Self {
+        Self::from(<Document<M> as Arango>::new(config))
+    }
+}
+
+impl<R, M> Repository<R, M>
+where
+    R: Serialize + DeserializeOwned + Sync + Send + 'static,
+    M: Metadata {
+    /// Serve get and get_many from a bounded LRU cache
+    pub fn with_cache(self, options: CacheOptions) -> Self {
+        Self::from(self.document.with_cache(options))
+    }
+
+    /// Run hooks around the writes of the repository
+    pub fn with_hooks<H: Hooks<R> + 'static>(self, hooks: H) -> Self
+    where
+        R: Clone {
+        Self::from(self.document.with_hooks(hooks))
+    }
+
+    /// Read a document by key
+    pub async fn get(&self, key: &(impl AsKey + ?Sized)) -> Result<Doc<R, M>> {
+        self.document.read(key).await
+    }
+
+    /// Read multiple documents by key, fails if one is missing
+    pub async fn get_many<S: Serialize + Sync + Send>(&self, keys: &[S]) -> Result<Vec<Doc<R, M>>> {
+        self.document.read_many(keys).await
+    }
+
+    /// Documents whose field equals value
+    pub async fn find_by(&self, field: impl Into<FieldRef>, value: impl Into<Value>) -> Result<Vec<Doc<R, M>>> {
+        self.find(&Filter::eq(field, value)).await
+    }
+
+    /// Documents matching filter
+    pub async fn find(&self, filter: &Filter) -> Result<Vec<Doc<R, M>>> {
+        let mut bindings: Bindings = Bindings::default();
+        let query: String = format!(
+            "FOR d IN {} FILTER {} RETURN d",
+            bindings.collection(&name::<R>()),
+            filter.render("d", &mut bindings)
+        );
+        self.all(query, bindings).await
+    }
+
+    /// Whether a document with key exists
+    pub async fn exists(&self, key: &(impl AsKey + ?Sized)) -> Result<bool> {
+        let key: DocumentKey = DocumentKey::new(key.key_in(&name::<R>())?)?;
+        self.document.exists(&DocumentId::of::<R>(key)).await
+    }
+
+    /// Number of documents in the collection
+    pub async fn count(&self) -> Result<u64> {
+        let mut bindings: Bindings = Bindings::default();
+        let query: String = format!("RETURN LENGTH({})", bindings.collection(&name::<R>()));
+        self.first(query, bindings).await
+    }
+
+    /// Number of documents matching filter
+    pub async fn count_where(&self, filter: &Filter) -> Result<u64> {
+        let mut bindings: Bindings = Bindings::default();
+        let query: String = format!(
+            "FOR d IN {} FILTER {} COLLECT WITH COUNT INTO total RETURN total",
+            bindings.collection(&name::<R>()),
+            filter.render("d", &mut bindings)
+        );
+        self.first(query, bindings).await
+    }
+
+    /// Insert a record, the stored document is returned
+    pub async fn insert(&self, record: &R) -> Result<Doc<R, M>> {
+        let result: WriteResult<R, M> = self.document.insert(record, &WriteOptions::default()).await?;
+        result.new.ok_or_else(|| ClientError::insertion_error(format!("insert into {} was not returned", name::<R>())))
+    }
+
+    /// Write the record of a document, the stored document is returned
+    pub async fn save(&self, doc: &Doc<R, M>) -> Result<Doc<R, M>> {
+        let result: WriteResult<R, M> = self.document.update(doc, &WriteOptions::default()).await?;
+        result.new.ok_or_else(|| ClientError::insertion_error(format!("update of {} was not returned", doc.keys.id)))
+    }
+
+    /// Delete a document by key
+    pub async fn delete(&self, key: &(impl AsKey + ?Sized)) -> Result<()> {
+        let _: WriteResult<R, M> = self.document.delete(key, &WriteOptions::default()).await?;
+        Ok(())
+    }
+
+    /// limit documents from offset, in the order of sort
+    /// Without a sort the order is unspecified, pages may overlap.
+    pub async fn page(&self, offset: u64, limit: u64, sort: &[Sort]) -> Result<Page<R, M>> {
+        let mut bindings: Bindings = Bindings::default();
+        let collection: &str = bindings.collection(&name::<R>());
+        let sort: String = Sort::render(sort, "d", &mut bindings);
+        let query: String = format!(
+            "LET items = (FOR d IN {0} {1} LIMIT {2}, {3} RETURN d) RETURN {{ items, total: LENGTH({0}) }}",
+            collection,
+            sort,
+            bindings.value(Value::from(offset)),
+            bindings.value(Value::from(limit))
+        );
+        self.first(query, bindings).await
+    }
+
+    /// Every document of the collection, fetched batch by batch
+    pub async fn stream_all(&self) -> Result<CursorStream<Doc<R, M>>> {
+        let mut bindings: Bindings = Bindings::default();
+        let query: String = format!("FOR d IN {} RETURN d", bindings.collection(&name::<R>()));
+        self.query.bound_cursor_stream(&bindings.request(query)).await
+    }
+
+    /// Every result of a query, across batches
+    pub(crate) async fn all<T: DeserializeOwned + Send + Sync>(&self, query: String, bindings: Bindings) -> Result<Vec<T>> {
+        self.query.collect(&bindings.request(query)).await
+    }
+
+    /// Single result of a query
+    pub(crate) async fn first<T: DeserializeOwned + Send + Sync>(&self, query: String, bindings: Bindings) -> Result<T> {
+        let response: CursorResponse<Vec<T>> = self.query.bound_cursor(&bindings.request(query)).await?;
+        response.result.into_iter()
+            .next()
+            .ok_or_else(|| ClientError::not_found_error(format!("query on {} returned nothing", name::<R>())))
+    }
+}
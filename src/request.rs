@@ -0,0 +1,320 @@
+use std::{fmt, io::Read};
+
+use hyper::{
+    Body, Request, Response,
+    body::Bytes,
+    header::{HeaderName, HeaderValue},
+    http::request::Builder
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+pub use hyper::{Method, StatusCode, HeaderMap};
+
+use crate::{Client, ClientError, ContentType, Encoding, Result, api::ApiError};
+
+/*
+    Escape hatch for ArangoDB endpoints blandango does not wrap (yet).
+
+    Requests go through the same pipeline as the wrapped APIs:
+    authentication, content negotiation and compression are applied by the client.
+*/
+
+/// Percent-encode every byte outside of the unreserved set and `allowed`.
+fn percent_encode(value: &str, allowed: &[u8]) -> String {
+    let mut encoded: String = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) || allowed.contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Sub-delimiters allowed within a path segment
+const SEGMENT: &[u8] = b"!$&'()*+,;=:@";
+
+/// A path segment with every other byte percent-encoded
+pub(crate) fn encode_segment(segment: &str) -> String {
+    percent_encode(segment, SEGMENT)
+}
+
+/// Builder for a request to an arbitrary endpoint of the current database.
+/// ```ignore
+/// let response: RawResponse = client.request(Method::GET)
+///     .path("_api")
+///     .path("version")
+///     .query("details", "true")
+///     .send()
+///     .await?;
+///
+/// let version: Version = response.decode()?;
+/// ```
+pub struct RequestBuilder<'a> {
+    client: &'a Client,
+    method: Method,
+    segments: Vec<String>,
+    query: Vec<String>,
+    headers: HeaderMap,
+    body: Option<Vec<u8>>,
+    error: Option<ClientError>
+}
+
+impl<'a> RequestBuilder<'a> {
+    pub fn new(client: &'a Client, method: Method) -> Self {
+        Self {
+            client,
+            method,
+            segments: vec![],
+            query: vec![],
+            headers: HeaderMap::new(),
+            body: None,
+            error: None
+        }
+    }
+
+    /// Append a path segment, it is percent-encoded.
+    pub fn path(mut self, segment: &str) -> Self {
+        self.segments.push(encode_segment(segment));
+        self
+    }
+
+    /// Append a query parameter, key and value are percent-encoded.
+    pub fn query<V: fmt::Display>(mut self, key: &str, value: V) -> Self {
+        self.query.push(format!("{}={}", percent_encode(key, &[]), percent_encode(&value.to_string(), &[])));
+        self
+    }
+
+    /// Append every field of a struct as query parameter, e.g. DocumentQueryParams.
+    pub fn params<P: Serialize>(mut self, params: &P) -> Self {
+        match serde_url_params::to_string(params) {
+            Ok(encoded) if !encoded.is_empty() => self.query.push(encoded),
+            Ok(_) => {},
+            Err(e) => self.fail(e.into()),
+        }
+        self
+    }
+
+    /// Set a header, overriding the defaults of the client.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        let header: std::result::Result<(HeaderName, HeaderValue), hyper::http::Error> = HeaderName::try_from(name)
+            .map_err(|e| e.into())
+            .and_then(|name| Ok((name, HeaderValue::try_from(value)?)));
+        match header {
+            Ok((name, value)) => {
+                self.headers.insert(name, value);
+            },
+            Err(e) => self.fail(e.into()),
+        }
+        self
+    }
+
+    /// Body encoded with the content type of the client.
+    pub fn body<K: Serialize>(mut self, data: &K) -> Self {
+        match self.client.content_type().encode(data) {
+            Ok(encoded) => self.body = Some(encoded),
+            Err(e) => self.fail(e),
+        }
+        self
+    }
+
+    /// Body sent as is, set the Content-Type header accordingly.
+    pub fn raw_body(mut self, body: Vec<u8>) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Endpoint relative to the database, e.g. `_api/version?details=true`
+    pub fn endpoint(&self) -> String {
+        let path: String = self.segments.join("/");
+        if self.query.is_empty() {
+            return path;
+        }
+        format!("{}?{}", path, self.query.join("&"))
+    }
+
+    pub async fn send(self) -> Result<RawResponse> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        let mut builder: Builder = self.client.builder(self.method.clone(), self.endpoint());
+        if let Some(h) = builder.headers_mut() {
+            h.extend(self.headers);
+        }
+        let request: Request<Body> = self.client.with_encoded(builder, self.body.unwrap_or_default())?;
+        let res: Response<Body> = self.client.send(request).await?;
+
+        let (parts, body) = res.into_parts();
+        let mut bytes: Bytes = hyper::body::to_bytes(body).await?;
+        if let Some(encoding) = Encoding::from_headers(&parts.headers) {
+            let mut inflated: Vec<u8> = Vec::new();
+            encoding.decoder(bytes.as_ref()).read_to_end(&mut inflated)?;
+            bytes = Bytes::from(inflated);
+        }
+
+        Ok(RawResponse {
+            status: parts.status,
+            headers: parts.headers,
+            body: bytes
+        })
+    }
+
+    fn fail(&mut self, error: ClientError) {
+        // keep the first error, it is reported by send
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+}
+
+/// Response of a raw request, the body is already decompressed.
+#[derive(Debug)]
+pub struct RawResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes
+}
+
+impl RawResponse {
+    pub fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Decode the body according to its Content-Type.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T> {
+        Client::decode(ContentType::from_headers(&self.headers), None, self.body.as_ref())
+    }
+
+    /// Turn an unsuccessful response into its ApiError.
+    /// Responses without body (e.g. HEAD) get an ApiError derived from the status.
+    pub fn error_for_status(self) -> Result<Self> {
+        if self.is_success() {
+            return Ok(self);
+        }
+        if self.body.is_empty() {
+            return Err(ApiError {
+                code: self.status.as_u16() as i32,
+                error: true,
+                error_message: self.status.canonical_reason().unwrap_or_default().to_owned(),
+                error_num: 0
+            }.into());
+        }
+        let err: ApiError = self.decode()?;
+        Err(err.into())
+    }
+}
+
+/// Typed descriptor of an endpoint.
+/// ```ignore
+/// struct Version;
+///
+/// impl Endpoint for Version {
+///     type Response = VersionResponse;
+///
+///     fn method(&self) -> Method { Method::GET }
+///     fn path(&self) -> Vec<String> { vec!["_api".to_owned(), "version".to_owned()] }
+/// }
+///
+/// let version: VersionResponse = client.call(&Version).await?;
+/// ```
+pub trait Endpoint {
+    type Response: DeserializeOwned;
+
+    fn method(&self) -> Method;
+
+    fn path(&self) -> Vec<String>;
+
+    /// Add query parameters, headers and body to the request.
+    fn prepare<'a>(&self, request: RequestBuilder<'a>) -> RequestBuilder<'a> {
+        request
+    }
+}
+
+impl Client {
+    /// Start a raw request against the current database.
+    pub fn request(&self, method: Method) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, method)
+    }
+
+    /// Call a typed endpoint, failing with the ApiError of unsuccessful responses.
+    pub async fn call<E: Endpoint>(&self, endpoint: &E) -> Result<E::Response> {
+        let mut request: RequestBuilder = self.request(endpoint.method());
+        for segment in endpoint.path() {
+            request = request.path(&segment);
+        }
+        let response: RawResponse = endpoint.prepare(request).send().await?;
+        response.error_for_status()?.decode()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Serialize;
+
+    use hyper::body::Bytes;
+
+    use crate::{Client, ClientError, Config, Method, DocumentQueryParams, RawResponse, StatusCode, HeaderMap};
+
+    #[derive(Serialize)]
+    struct Body {
+        name: String
+    }
+
+    fn client() -> Client {
+        Client::new(&Config {
+            host: "http://localhost:8529".to_owned(),
+            database: "fake".to_owned(),
+            user: String::new(),
+            password: String::new(),
+        })
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let client: Client = client();
+
+        let endpoint: String = client.request(Method::GET)
+            .path("_api")
+            .path("document")
+            .path("some collection/key")
+            .query("onlyget", true)
+            .params(&DocumentQueryParams::default())
+            .endpoint();
+
+        assert_eq!(endpoint, "_api/document/some%20collection%2Fkey?onlyget=true&waitForSync=true&returnNew=true");
+    }
+
+    #[test]
+    fn test_error_without_body() {
+        let response: RawResponse = RawResponse {
+            status: StatusCode::NOT_FOUND,
+            headers: HeaderMap::new(),
+            body: Bytes::new()
+        };
+
+        match response.error_for_status() {
+            Err(ClientError::Api(e)) => assert_eq!(e.code, 404),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_header() {
+        let client: Client = client();
+
+        let result = client.request(Method::POST)
+            .path("_api")
+            .header("bad header", "value")
+            .body(&Body { name: "name".to_owned() })
+            .send()
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,464 @@
+use std::{io::{self, Write}, marker::PhantomData};
+
+use futures_util::{Stream, stream};
+use hyper::{Body, body::HttpBody};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use flate2::write::{GzDecoder, ZlibDecoder};
+
+use crate::{Result, Encoding};
+
+/*
+    Incremental deserialization of array shaped response bodies.
+
+    The body is consumed chunk by chunk and every element of the target array
+    is deserialized as soon as it is complete, so peak memory is bounded by
+    the size of a single element rather than the size of the response.
+
+    The target array is either the body itself (`[...]`) or a top level attribute
+    of an object body (`{"result": [...], ...}`). The other attributes of the object
+    are collected into an envelope, available once the stream has been drained.
+*/
+
+enum Step {
+    Element(Vec<u8>),
+    NeedData,
+    Done
+}
+
+enum State {
+    Start,
+    Key,
+    Colon(String),
+    Value(String),
+    Items,
+    Done
+}
+
+/// Incremental decompression of compressed response bodies.
+enum Inflater {
+    Gzip(GzDecoder<Vec<u8>>),
+    Deflate(ZlibDecoder<Vec<u8>>)
+}
+
+impl Inflater {
+    fn new(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Gzip => Self::Gzip(GzDecoder::new(Vec::new())),
+            Encoding::Deflate => Self::Deflate(ZlibDecoder::new(Vec::new())),
+        }
+    }
+
+    fn inflate(&mut self, chunk: &[u8], output: &mut Vec<u8>) -> io::Result<()> {
+        let inflated: &mut Vec<u8> = match self {
+            Self::Gzip(decoder) => {
+                decoder.write_all(chunk)?;
+                decoder.get_mut()
+            },
+            Self::Deflate(decoder) => {
+                decoder.write_all(chunk)?;
+                decoder.get_mut()
+            },
+        };
+        output.append(inflated);
+        Ok(())
+    }
+
+    fn finish(&mut self, output: &mut Vec<u8>) -> io::Result<()> {
+        let inflated: &mut Vec<u8> = match self {
+            Self::Gzip(decoder) => {
+                decoder.try_finish()?;
+                decoder.get_mut()
+            },
+            Self::Deflate(decoder) => {
+                decoder.try_finish()?;
+                decoder.get_mut()
+            },
+        };
+        output.append(inflated);
+        Ok(())
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\n' | b'\r' | b'\t')
+}
+
+/// Progress of the scan over an incomplete value.
+/// Kept across refills, so every byte of a large element is scanned once.
+#[derive(Default)]
+struct Scan {
+    /// Scanned bytes from the start of the value
+    offset: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool
+}
+
+impl Scan {
+    /// End offset (exclusive) of the JSON value starting at `start`.
+    /// Returns None when the buffer does not hold the complete value yet,
+    /// the next call resumes where this one stopped.
+    fn value_end(&mut self, buffer: &[u8], start: usize, eof: bool) -> Option<usize> {
+        let end: Option<usize> = match buffer[start] {
+            b'"' | b'{' | b'[' => self.nested_end(buffer, start),
+            _ => {
+                // numbers, true, false and null end at the next delimiter
+                let from: usize = start + self.offset;
+                let end: Option<usize> = buffer[from..].iter()
+                    .position(|&byte| matches!(byte, b',' | b']' | b'}') || is_whitespace(byte))
+                    .map(|offset| from + offset);
+                match end {
+                    Some(end) => Some(end),
+                    None if eof => Some(buffer.len()),
+                    None => {
+                        self.offset = buffer.len() - start;
+                        None
+                    },
+                }
+            },
+        };
+        if end.is_some() {
+            *self = Self::default();
+        }
+        end
+    }
+
+    /// End of a string, object or array
+    fn nested_end(&mut self, buffer: &[u8], start: usize) -> Option<usize> {
+        let mut index: usize = start + self.offset;
+        while index < buffer.len() {
+            let byte: u8 = buffer[index];
+            index += 1;
+            if self.escaped {
+                self.escaped = false;
+                continue;
+            }
+            if self.in_string {
+                match byte {
+                    b'\\' => self.escaped = true,
+                    b'"' => {
+                        self.in_string = false;
+                        if self.depth == 0 {
+                            return Some(index);
+                        }
+                    },
+                    _ => {}
+                }
+            } else {
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'{' | b'[' => self.depth += 1,
+                    b'}' | b']' => {
+                        self.depth -= 1;
+                        if self.depth == 0 {
+                            return Some(index);
+                        }
+                    },
+                    _ => {}
+                }
+            }
+        }
+        self.offset = index - start;
+        None
+    }
+}
+
+/// Incrementally deserialized elements of an array shaped response body.
+/// ```ignore
+/// let mut stream: ArrayStream<Doc<Sample>> = document.read_many_stream(&keys).await?;
+/// while let Some(doc) = stream.next().await? {
+///     println!("{:#?}", doc);
+/// }
+/// ```
+pub struct ArrayStream<T> {
+    body: Body,
+    inflater: Option<Inflater>,
+    field: Option<String>,
+    state: State,
+    scan: Scan,
+    buffer: Vec<u8>,
+    position: usize,
+    eof: bool,
+    envelope: Map<String, Value>,
+    _marker: PhantomData<T>
+}
+
+impl<T: DeserializeOwned> ArrayStream<T> {
+    /// Stream the elements of the array at `field` of an object body,
+    /// or of the body itself when `field` is None.
+    pub(crate) fn new(body: Body, encoding: Option<Encoding>, field: Option<&str>) -> Self {
+        Self {
+            body,
+            inflater: encoding.map(Inflater::new),
+            field: field.map(|f| f.to_owned()),
+            state: State::Start,
+            scan: Scan::default(),
+            buffer: Vec::new(),
+            position: 0,
+            eof: false,
+            envelope: Map::new(),
+            _marker: PhantomData
+        }
+    }
+
+    /// Next element of the array, None once the body has been consumed.
+    pub async fn next(&mut self) -> Result<Option<T>> {
+        loop {
+            match self.advance()? {
+                Step::Element(element) => return Ok(Some(serde_json::from_slice(&element)?)),
+                Step::NeedData => self.fill().await?,
+                Step::Done => return Ok(None),
+            }
+        }
+    }
+
+    /// Remaining attributes of an object body, excluding the streamed array.
+    /// Drains the stream if it has not been exhausted yet.
+    pub async fn envelope(&mut self) -> Result<&Map<String, Value>> {
+        while self.next().await?.is_some() {}
+        Ok(&self.envelope)
+    }
+
+    /// Skip the remaining elements without deserializing them.
+    /// Returns the envelope of an object body.
+    pub(crate) async fn finish(&mut self) -> Result<&Map<String, Value>> {
+        loop {
+            match self.advance()? {
+                Step::Element(_) => {},
+                Step::NeedData => self.fill().await?,
+                Step::Done => return Ok(&self.envelope),
+            }
+        }
+    }
+
+    /// Convert into a `futures` Stream of elements.
+    pub fn into_stream(self) -> impl Stream<Item = Result<T>> {
+        stream::unfold(Some(self), |state| async move {
+            let mut array: ArrayStream<T> = state?;
+            match array.next().await {
+                Ok(Some(element)) => Some((Ok(element), Some(array))),
+                Ok(None) => None,
+                // end the stream after the first error
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Read the next chunk of the body into the buffer.
+    async fn fill(&mut self) -> Result<()> {
+        if self.eof {
+            return Err(invalid("unexpected end of response body").into());
+        }
+
+        // release consumed bytes before growing the buffer
+        self.buffer.drain(..self.position);
+        self.position = 0;
+
+        match self.body.data().await {
+            Some(chunk) => {
+                let chunk = chunk?;
+                match self.inflater.as_mut() {
+                    Some(inflater) => inflater.inflate(&chunk, &mut self.buffer)?,
+                    None => self.buffer.extend_from_slice(&chunk),
+                }
+            },
+            None => {
+                self.eof = true;
+                if let Some(inflater) = self.inflater.as_mut() {
+                    inflater.finish(&mut self.buffer)?;
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) -> Option<u8> {
+        while self.position < self.buffer.len() {
+            let byte: u8 = self.buffer[self.position];
+            if !is_whitespace(byte) {
+                return Some(byte);
+            }
+            self.position += 1;
+        }
+        None
+    }
+
+    /// Run the scanner over the buffered bytes until an element is complete,
+    /// more bytes are needed or the body is exhausted.
+    fn advance(&mut self) -> Result<Step> {
+        loop {
+            if matches!(self.state, State::Done) {
+                return Ok(Step::Done);
+            }
+            let Some(byte) = self.skip_whitespace() else {
+                return Ok(Step::NeedData);
+            };
+
+            match std::mem::replace(&mut self.state, State::Done) {
+                State::Start => {
+                    self.state = match (byte, &self.field) {
+                        (b'[', None) => State::Items,
+                        (b'{', Some(_)) => State::Key,
+                        _ => return Err(invalid("unexpected response body shape").into()),
+                    };
+                    self.position += 1;
+                },
+                State::Key => match byte {
+                    b',' => {
+                        self.position += 1;
+                        self.state = State::Key;
+                    },
+                    b'}' => {
+                        self.position += 1;
+                        self.state = State::Done;
+                    },
+                    b'"' => {
+                        let Some(end) = self.scan.value_end(&self.buffer, self.position, self.eof) else {
+                            self.state = State::Key;
+                            return Ok(Step::NeedData);
+                        };
+                        let key: String = serde_json::from_slice(&self.buffer[self.position..end])?;
+                        self.position = end;
+                        self.state = State::Colon(key);
+                    },
+                    _ => return Err(invalid("expected an attribute name").into()),
+                },
+                State::Colon(key) => {
+                    if byte != b':' {
+                        return Err(invalid("expected ':'").into());
+                    }
+                    self.position += 1;
+                    self.state = State::Value(key);
+                },
+                State::Value(key) => {
+                    if byte == b'[' && self.field.as_deref() == Some(key.as_str()) {
+                        self.position += 1;
+                        self.state = State::Items;
+                        continue;
+                    }
+                    let Some(end) = self.scan.value_end(&self.buffer, self.position, self.eof) else {
+                        self.state = State::Value(key);
+                        return Ok(Step::NeedData);
+                    };
+                    let value: Value = serde_json::from_slice(&self.buffer[self.position..end])?;
+                    self.envelope.insert(key, value);
+                    self.position = end;
+                    self.state = State::Key;
+                },
+                State::Items => match byte {
+                    b',' => {
+                        self.position += 1;
+                        self.state = State::Items;
+                    },
+                    b']' => {
+                        self.position += 1;
+                        self.state = if self.field.is_some() { State::Key } else { State::Done };
+                    },
+                    _ => {
+                        self.state = State::Items;
+                        let Some(end) = self.scan.value_end(&self.buffer, self.position, self.eof) else {
+                            return Ok(Step::NeedData);
+                        };
+                        let element: Vec<u8> = self.buffer[self.position..end].to_vec();
+                        self.position = end;
+                        return Ok(Step::Element(element));
+                    },
+                },
+                State::Done => return Ok(Step::Done),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use futures_util::{stream, StreamExt};
+    use hyper::Body;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use crate::{ArrayStream, Encoding};
+
+    use super::Scan;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Item {
+        body: String
+    }
+
+    /// Body delivered in chunks of `size` bytes to exercise every split point.
+    fn chunked(data: &[u8], size: usize) -> Body {
+        let chunks: Vec<io::Result<Vec<u8>>> = data.chunks(size).map(|c| Ok(c.to_vec())).collect();
+        Body::wrap_stream(stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn test_array_body() {
+        let data: &[u8] = br#" [ {"body": "a]\"}"}, {"body": "b"} ,{"body":"[c"}] "#;
+
+        for size in 1..data.len() {
+            let mut array: ArrayStream<Item> = ArrayStream::new(chunked(data, size), None, None);
+            let mut items: Vec<String> = vec![];
+            while let Some(item) = array.next().await.unwrap() {
+                items.push(item.body);
+            }
+            assert_eq!(items, vec!["a]\"}", "b", "[c"]);
+        }
+    }
+
+    #[test]
+    fn test_resumed_scan() {
+        let data: &[u8] = br#"{"body": "a\"}"} "#;
+        let end: usize = data.len() - 1;
+
+        // every refill continues from the bytes scanned before
+        let mut scan: Scan = Scan::default();
+        for size in 1..end {
+            assert_eq!(scan.value_end(&data[..size], 0, false), None);
+            assert_eq!(scan.offset, size);
+        }
+        assert_eq!(scan.value_end(data, 0, false), Some(end));
+        assert_eq!(scan.offset, 0);
+    }
+
+    #[tokio::test]
+    async fn test_envelope() {
+        let data: &[u8] = br#"{"count": 3, "result": [1, -2.5e3, null], "hasMore": false, "extra": {"warnings": []}}"#;
+
+        for size in 1..data.len() {
+            let mut array: ArrayStream<Option<f64>> = ArrayStream::new(chunked(data, size), None, Some("result"));
+            let mut items: Vec<Option<f64>> = vec![];
+            while let Some(item) = array.next().await.unwrap() {
+                items.push(item);
+            }
+            assert_eq!(items, vec![Some(1.0), Some(-2500.0), None]);
+
+            let envelope: Value = Value::Object(array.envelope().await.unwrap().clone());
+            assert_eq!(envelope, json!({"count": 3, "hasMore": false, "extra": {"warnings": []}}));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compressed_stream() {
+        let data: Vec<u8> = serde_json::to_vec(&json!([{"body": "x"}, {"body": "y"}])).unwrap();
+        let compressed: Vec<u8> = Encoding::Gzip.compress(&data, 6).unwrap();
+
+        let array: ArrayStream<Item> = ArrayStream::new(chunked(&compressed, 7), Some(Encoding::Gzip), None);
+        let items: Vec<Item> = array.into_stream().map(|item| item.unwrap()).collect().await;
+        assert_eq!(items, vec![Item { body: "x".to_owned() }, Item { body: "y".to_owned() }]);
+    }
+
+    #[tokio::test]
+    async fn test_truncated_body() {
+        let mut array: ArrayStream<Item> = ArrayStream::new(chunked(br#"[{"body": "a"}, {"bo"#, 4), None, None);
+        assert!(array.next().await.unwrap().is_some());
+        assert!(array.next().await.is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::{Result, AsKey, DocumentRevision, Written, Metadata, Timestamps, Doc, Document, DocumentRouter, DocumentQueryParams, Router, WriteOptions, WriteResult, name};
+
+/*
+    Change tracking for documents.
+    The record is snapshotted when it is read, saving only sends
+    the attributes that differ from the snapshot.
+*/
+
+/// A document remembering its record as it was read.
+/// ```ignore
+/// let mut message: Tracked<SampleData> = document.read_tracked(&key).await?;
+/// message.record.flag = true;
+///
+/// // PATCH { "_rev": "...", "flag": true }
+/// document.save(&mut message).await?;
+/// ```
+#[derive(Debug)]
+pub struct Tracked<R, M = Timestamps>
+where
+    R: Sized + Sync + Send {
+    doc: Doc<R, M>,
+    snapshot: Value
+}
+
+impl<R, M> Tracked<R, M>
+where
+    R: Serialize + DeserializeOwned + Sync + Send {
+    pub fn new(doc: Doc<R, M>) -> Result<Self> {
+        let snapshot: Value = serde_json::to_value(&doc.record)?;
+        Ok(Self { doc, snapshot })
+    }
+
+    /// Attributes changed since the snapshot, None when nothing changed.
+    /// Attributes set to null are changes, removed attributes are not.
+    pub fn changes(&self) -> Result<Option<Map<String, Value>>> {
+        let current: Value = serde_json::to_value(&self.doc.record)?;
+        // records are flattened into the document, they are always objects
+        match diff(&self.snapshot, &current) {
+            Some(Value::Object(changes)) => Ok(Some(changes)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Attributes removed since the snapshot set to null, None when nothing was removed.
+    pub fn removals(&self) -> Result<Option<Map<String, Value>>> {
+        let current: Value = serde_json::to_value(&self.doc.record)?;
+        match removals(&self.snapshot, &current) {
+            Some(Value::Object(removals)) => Ok(Some(removals)),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn into_inner(self) -> Doc<R, M> {
+        self.doc
+    }
+}
+
+impl<R, M> Deref for Tracked<R, M>
+where
+    R: Sized + Sync + Send {
+    type Target = Doc<R, M>;
+
+    fn deref(&self) -> &Doc<R, M> {
+        &self.doc
+    }
+}
+
+impl<R, M> DerefMut for Tracked<R, M>
+where
+    R: Sized + Sync + Send {
+    fn deref_mut(&mut self) -> &mut Doc<R, M> {
+        &mut self.doc
+    }
+}
+
+/// Changes turning `old` into `new`, leaving out removed attributes.
+/// Objects are compared attribute by attribute, any other value is replaced as a whole.
+fn diff(old: &Value, new: &Value) -> Option<Value> {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut changes: Map<String, Value> = Map::new();
+            for (key, value) in new {
+                match old.get(key) {
+                    Some(previous) => {
+                        if let Some(change) = diff(previous, value) {
+                            changes.insert(key.clone(), change);
+                        }
+                    },
+                    None => {
+                        changes.insert(key.clone(), value.clone());
+                    },
+                }
+            }
+            (!changes.is_empty()).then_some(Value::Object(changes))
+        },
+        (old, new) if old == new => None,
+        (_, new) => Some(new.clone()),
+    }
+}
+
+/// Attributes of `old` objects missing from `new`, set to null.
+fn removals(old: &Value, new: &Value) -> Option<Value> {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut removed: Map<String, Value> = Map::new();
+            for (key, previous) in old {
+                match new.get(key) {
+                    Some(value) => {
+                        if let Some(removal) = removals(previous, value) {
+                            removed.insert(key.clone(), removal);
+                        }
+                    },
+                    None => {
+                        removed.insert(key.clone(), Value::Null);
+                    },
+                }
+            }
+            (!removed.is_empty()).then_some(Value::Object(removed))
+        },
+        _ => None,
+    }
+}
+
+impl<M: Metadata> Document<M> {
+    /// Read a document by key and track changes to its record
+    pub async fn read_tracked<R: Serialize + DeserializeOwned + Sync + Send>(&self, key: &(impl AsKey + ?Sized)) -> Result<Tracked<R, M>> {
+        Tracked::new(self.read(key).await?)
+    }
+
+    /// Patch the changed attributes of a tracked document.
+    /// Attributes set to null are stored as null, removed attributes are unset by a second patch.
+    /// Fails with a precondition error if the document was modified since it was read.
+    /// Returns None without a request when nothing changed.
+    /// The tracked document is refreshed with the stored version, which is taken out of the result.
+    pub async fn save<R: Serialize + DeserializeOwned + Sync + Send + 'static>(&self, tracked: &mut Tracked<R, M>) -> Result<Option<WriteResult<R, M>>> {
+        let key: &str = &tracked.doc.keys.key;
+        let mut rev: DocumentRevision = tracked.doc.keys.rev.clone();
+
+        // nulls are kept in the changes, the nulls of the removals unset their attributes
+        let mut saved: Option<WriteResult<R, M>> = None;
+        for (patch, keep_null) in [(tracked.changes()?, true), (tracked.removals()?, false)] {
+            let mut patch: Map<String, Value> = match patch {
+                Some(patch) => patch,
+                None => continue,
+            };
+            self.run_before_patch::<R>(key, &patch)?;
+            patch.insert("_rev".to_owned(), Value::from(rev.as_str()));
+
+            let options: WriteOptions = WriteOptions { keep_null: Some(keep_null), ..Default::default() };
+            let mut params: DocumentQueryParams = options.params();
+            params.ignore_revs = Some(false);
+
+            let result: Result<WriteResult<R, M>> = self.client.patch(Router::key_with_params(&name::<R>(), key, params)?, &patch).await;
+            self.invalidate::<R>(key);
+
+            let result: WriteResult<R, M> = result?;
+            self.run_after_update(key, &Written::of(None, &result));
+            if let Some(written) = result.rev() {
+                rev = written.clone();
+            }
+            saved = Some(result);
+        }
+
+        let mut result: WriteResult<R, M> = match saved {
+            Some(result) => result,
+            None => return Ok(None),
+        };
+        if let Some(new) = result.new.take() {
+            *tracked = Tracked::new(new)?;
+        }
+        Ok(Some(result))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{diff, removals};
+
+    #[test]
+    fn test_diff() {
+        let old = json!({ "body": "body", "flag": false, "nested": { "a": 1, "b": 2 }, "tags": ["a"], "note": "note" });
+
+        assert_eq!(diff(&old, &old), None);
+
+        let new = json!({ "body": "body", "flag": true, "nested": { "a": 1, "b": 3 }, "tags": ["a", "b"] });
+        assert_eq!(diff(&old, &new), Some(json!({ "flag": true, "nested": { "b": 3 }, "tags": ["a", "b"] })));
+        assert_eq!(removals(&old, &new), Some(json!({ "note": null })));
+
+        let new = json!({ "body": "body", "flag": false, "nested": null, "tags": ["a"], "note": "note", "added": 1 });
+        assert_eq!(diff(&old, &new), Some(json!({ "nested": null, "added": 1 })));
+        assert_eq!(removals(&old, &new), None);
+
+        let new = json!({ "body": "body", "flag": false, "nested": { "a": 1 }, "tags": ["a"], "note": null });
+        assert_eq!(diff(&old, &new), Some(json!({ "note": null })));
+        assert_eq!(removals(&old, &new), Some(json!({ "nested": { "b": null } })));
+    }
+}
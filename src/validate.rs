@@ -0,0 +1,89 @@
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Result, ClientError, ArangoKeys, Doc, Document, Metadata, Validator, WriteOptions, WriteResult};
+
+/*
+    Validating document writes.
+    Records are validated before anything is sent, an invalid record never reaches the server.
+*/
+
+/// Validate every record, all failures are returned with the index of their record
+fn validate_all<R: Validator>(records: &[R]) -> Result<()> {
+    let failures: Vec<(usize, ClientError)> = records.iter()
+        .enumerate()
+        .filter_map(|(index, record)| record.validate().err().map(|error| (index, error)))
+        .collect();
+    if failures.is_empty() {
+        return Ok(());
+    }
+    Err(ClientError::ValidationFailures(failures))
+}
+
+impl<M: Metadata> Document<M> {
+    /// Insert a document after validating it
+    pub async fn insert_validated<R: Validator + DeserializeOwned + Sync + Send + 'static>(&self, model: &R, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        model.validate()?;
+        self.insert(model, options).await
+    }
+
+    /// Insert many documents, nothing is inserted unless every record is valid
+    pub async fn insert_many_validated<R: Validator + Serialize + DeserializeOwned + Sync + Send + 'static>(&self, models: &[R]) -> Result<Vec<ArangoKeys>> {
+        validate_all(models)?;
+        self.insert_many(models).await
+    }
+
+    /// Update a document after validating its record
+    pub async fn update_validated<R: Validator + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        doc.record.validate()?;
+        self.update(doc, options).await
+    }
+
+    /// Replace a document after validating its record
+    pub async fn replace_validated<R: Validator + DeserializeOwned + Sync + Send + 'static>(&self, doc: &Doc<R, M>, options: &WriteOptions) -> Result<WriteResult<R, M>> {
+        doc.record.validate()?;
+        self.replace(doc, options).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Serialize;
+
+    use crate::{ClientError, Validator};
+
+    use super::validate_all;
+
+    #[derive(Serialize)]
+    struct Sample {
+        body: String
+    }
+
+    impl Validator for Sample {
+        fn validate(&self) -> Result<(), ClientError> {
+            if self.body.is_empty() {
+                return Err(ClientError::validation_error("empty body".to_owned()));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_validate_all() {
+        let valid: Vec<Sample> = vec![Sample { body: "body".to_owned() }];
+        assert!(validate_all(&valid).is_ok());
+
+        let records: Vec<Sample> = vec![
+            Sample { body: "".to_owned() },
+            Sample { body: "body".to_owned() },
+            Sample { body: "".to_owned() }
+        ];
+        match validate_all(&records) {
+            Err(ClientError::ValidationFailures(failures)) => {
+                let indexes: Vec<usize> = failures.iter().map(|(index, _)| *index).collect();
+                assert_eq!(indexes, vec![0, 2]);
+                assert!(matches!(failures[0].1, ClientError::Validation(_)));
+            },
+            _ => panic!("expected validation failures"),
+        }
+    }
+}
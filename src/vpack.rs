@@ -0,0 +1,1041 @@
+use std::{fmt, error, str};
+
+use serde::{
+    Serialize, de::{self, DeserializeOwned, Deserializer as _, Visitor, SeqAccess, MapAccess, EnumAccess, VariantAccess, IntoDeserializer},
+    ser::{self, SerializeSeq, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, SerializeMap, SerializeStruct, SerializeStructVariant}
+};
+
+/*
+    Minimal serde implementation of the VelocyPack format.
+    https://github.com/arangodb/velocypack/blob/main/VelocyPack.md
+
+    Serialization always emits compact arrays (0x13) and compact objects (0x14)
+    which ArangoDB accepts for request bodies.
+    Deserialization understands every array and object layout the server produces,
+    translated attribute names (_key, _rev, _id, _from, _to), integers, doubles,
+    strings, binary blobs and UTC dates (as i64 milliseconds).
+    Custom types, BCD numbers and external pointers are not supported.
+*/
+
+pub const CONTENT_TYPE: &str = "application/x-velocypack";
+
+#[derive(Debug)]
+pub struct Error {
+    message: String
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    fn new(message: &str) -> Self {
+        Self { message: message.to_owned() }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "velocypack error: {}", self.message)
+    }
+}
+
+impl error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self { message: msg.to_string() }
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self { message: msg.to_string() }
+    }
+}
+
+/// Serialize a value into a VelocyPack buffer
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut serializer: Serializer = Serializer { output: Vec::new() };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+/// Deserialize a value from a VelocyPack buffer
+pub fn from_slice<T: DeserializeOwned>(input: &[u8]) -> Result<T> {
+    let mut deserializer: Deserializer = Deserializer { input, position: 0 };
+    let value: T = T::deserialize(&mut deserializer)?;
+    Ok(value)
+}
+
+// Encoding helpers
+
+fn varint(mut value: u64) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::new();
+    loop {
+        let byte: u8 = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn minimal_width(value: u64) -> usize {
+    let mut width: usize = 1;
+    while width < 8 && value >> (width * 8) != 0 {
+        width += 1;
+    }
+    width
+}
+
+pub struct Serializer {
+    output: Vec<u8>
+}
+
+impl Serializer {
+    fn write_uint(&mut self, value: u64) {
+        if value <= 9 {
+            self.output.push(0x30 + value as u8);
+            return;
+        }
+        let width: usize = minimal_width(value);
+        self.output.push(0x27 + width as u8);
+        self.output.extend_from_slice(&value.to_le_bytes()[..width]);
+    }
+
+    fn write_int(&mut self, value: i64) {
+        if (0..=9).contains(&value) {
+            self.output.push(0x30 + value as u8);
+            return;
+        }
+        if (-6..0).contains(&value) {
+            self.output.push((0x40 + value) as u8);
+            return;
+        }
+        let mut width: usize = 1;
+        while width < 8 {
+            let bits: u32 = (width * 8) as u32 - 1;
+            if value >= -(1i64 << bits) && value < (1i64 << bits) {
+                break;
+            }
+            width += 1;
+        }
+        self.output.push(0x1f + width as u8);
+        self.output.extend_from_slice(&value.to_le_bytes()[..width]);
+    }
+
+    fn write_str(&mut self, value: &str) {
+        let bytes: &[u8] = value.as_bytes();
+        if bytes.len() <= 126 {
+            self.output.push(0x40 + bytes.len() as u8);
+        } else {
+            self.output.push(0xbf);
+            self.output.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+        }
+        self.output.extend_from_slice(bytes);
+    }
+
+    fn open(&mut self, head: u8) -> Compound<'_> {
+        let start: usize = self.output.len();
+        self.output.push(head);
+        Compound { serializer: self, start, items: 0 }
+    }
+}
+
+/// An open compact array or object, closed by `finish`.
+pub struct Compound<'a> {
+    serializer: &'a mut Serializer,
+    start: usize,
+    items: u64
+}
+
+impl<'a> Compound<'a> {
+    fn finish(self) {
+        let output: &mut Vec<u8> = &mut self.serializer.output;
+        if self.items == 0 {
+            // empty array (0x01) or empty object (0x0a)
+            output[self.start] = if output[self.start] == 0x13 { 0x01 } else { 0x0a };
+            return;
+        }
+
+        let mut count: Vec<u8> = varint(self.items);
+        count.reverse();
+
+        let payload: usize = output.len() - self.start - 1;
+        let mut width: usize = 1;
+        let total: usize = loop {
+            let total: usize = 1 + width + payload + count.len();
+            let needed: usize = varint(total as u64).len();
+            if needed == width {
+                break total;
+            }
+            width = needed;
+        };
+
+        let position: usize = self.start + 1;
+        output.splice(position..position, varint(total as u64));
+        output.extend_from_slice(&count);
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Variant<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Variant<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.output.push(if v { 0x1a } else { 0x19 });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> { self.serialize_i64(v as i64) }
+    fn serialize_i16(self, v: i16) -> Result<()> { self.serialize_i64(v as i64) }
+    fn serialize_i32(self, v: i32) -> Result<()> { self.serialize_i64(v as i64) }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.write_int(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> { self.serialize_u64(v as u64) }
+    fn serialize_u16(self, v: u16) -> Result<()> { self.serialize_u64(v as u64) }
+    fn serialize_u32(self, v: u32) -> Result<()> { self.serialize_u64(v as u64) }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.write_uint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> { self.serialize_f64(v as f64) }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.output.push(0x1b);
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.write_str(v.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.write_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        let width: usize = minimal_width(v.len() as u64);
+        self.output.push(0xbf + width as u8);
+        self.output.extend_from_slice(&(v.len() as u64).to_le_bytes()[..width]);
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.output.push(0x18);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<()> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<()> {
+        let mut object: Compound = self.open(0x14);
+        object.serializer.write_str(variant);
+        value.serialize(&mut *object.serializer)?;
+        object.items += 1;
+        object.finish();
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>> {
+        Ok(self.open(0x13))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, _len: usize) -> Result<Variant<'a>> {
+        Variant::open(self, variant, 0x13)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>> {
+        Ok(self.open(0x14))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, _len: usize) -> Result<Variant<'a>> {
+        Variant::open(self, variant, 0x14)
+    }
+}
+
+impl<'a> SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut *self.serializer)?;
+        self.items += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<()> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<()> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(KeySerializer { serializer: &mut *self.serializer })
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut *self.serializer)?;
+        self.items += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.serializer.write_str(key);
+        SerializeMap::serialize_value(self, value)
+    }
+
+    fn end(self) -> Result<()> {
+        SerializeMap::end(self)
+    }
+}
+
+/// Enum variants with content are written as `{ variant: content }`.
+pub struct Variant<'a> {
+    outer: Compound<'a>,
+    inner_start: usize,
+    inner_items: u64
+}
+
+impl<'a> Variant<'a> {
+    fn open(serializer: &'a mut Serializer, variant: &str, head: u8) -> Result<Self> {
+        let outer: Compound = serializer.open(0x14);
+        outer.serializer.write_str(variant);
+        let inner_start: usize = outer.serializer.output.len();
+        outer.serializer.output.push(head);
+        Ok(Self { outer, inner_start, inner_items: 0 })
+    }
+
+    fn finish(mut self) {
+        Compound {
+            serializer: &mut *self.outer.serializer,
+            start: self.inner_start,
+            items: self.inner_items
+        }.finish();
+        self.outer.items = 1;
+        self.outer.finish();
+    }
+}
+
+impl<'a> SerializeTupleVariant for Variant<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut *self.outer.serializer)?;
+        self.inner_items += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish();
+        Ok(())
+    }
+}
+
+impl<'a> SerializeStructVariant for Variant<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.outer.serializer.write_str(key);
+        value.serialize(&mut *self.outer.serializer)?;
+        self.inner_items += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish();
+        Ok(())
+    }
+}
+
+/// Object keys must be strings, integer keys are written as strings (like serde_json).
+struct KeySerializer<'a> {
+    serializer: &'a mut Serializer
+}
+
+impl<'a> KeySerializer<'a> {
+    fn key<T: fmt::Display>(self, value: T) -> Result<()> {
+        self.serializer.write_str(&value.to_string());
+        Ok(())
+    }
+}
+
+fn key_error() -> Error {
+    Error::new("object keys must be strings")
+}
+
+impl<'a> ser::Serializer for KeySerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> { self.key(v) }
+    fn serialize_i8(self, v: i8) -> Result<()> { self.key(v) }
+    fn serialize_i16(self, v: i16) -> Result<()> { self.key(v) }
+    fn serialize_i32(self, v: i32) -> Result<()> { self.key(v) }
+    fn serialize_i64(self, v: i64) -> Result<()> { self.key(v) }
+    fn serialize_u8(self, v: u8) -> Result<()> { self.key(v) }
+    fn serialize_u16(self, v: u16) -> Result<()> { self.key(v) }
+    fn serialize_u32(self, v: u32) -> Result<()> { self.key(v) }
+    fn serialize_u64(self, v: u64) -> Result<()> { self.key(v) }
+    fn serialize_f32(self, _v: f32) -> Result<()> { Err(key_error()) }
+    fn serialize_f64(self, _v: f64) -> Result<()> { Err(key_error()) }
+    fn serialize_char(self, v: char) -> Result<()> { self.key(v) }
+    fn serialize_str(self, v: &str) -> Result<()> { self.key(v) }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<()> { Err(key_error()) }
+    fn serialize_none(self) -> Result<()> { Err(key_error()) }
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<()> { Err(key_error()) }
+    fn serialize_unit(self) -> Result<()> { Err(key_error()) }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> { Err(key_error()) }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<()> {
+        self.key(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, _variant: &'static str, _value: &T) -> Result<()> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> { Err(key_error()) }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> { Err(key_error()) }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> { Err(key_error()) }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant> {
+        Err(key_error())
+    }
+}
+
+// Decoding
+
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+    position: usize
+}
+
+fn eof() -> Error {
+    Error::new("unexpected end of input")
+}
+
+/// Width in bytes of the byte length field of the array/object heads 0x02..0x12.
+fn length_width(head: u8) -> usize {
+    let offset: u8 = match head {
+        0x02..=0x05 => head - 0x02,
+        0x06..=0x09 => head - 0x06,
+        0x0b..=0x0e => head - 0x0b,
+        _ => head - 0x0f,
+    };
+    1 << offset
+}
+
+/// Translated attribute names used by ArangoDB for the system attributes.
+fn translate(id: u64) -> Result<&'static str> {
+    match id {
+        1 => Ok("_key"),
+        2 => Ok("_rev"),
+        3 => Ok("_id"),
+        4 => Ok("_from"),
+        5 => Ok("_to"),
+        _ => Err(Error::new("unknown attribute translation")),
+    }
+}
+
+impl<'de> Deserializer<'de> {
+    fn peek(&self) -> Result<u8> {
+        self.input.get(self.position).copied().ok_or_else(eof)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8]> {
+        let end: usize = self.position.checked_add(len).ok_or_else(eof)?;
+        let bytes: &'de [u8] = self.input.get(self.position..end).ok_or_else(eof)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_le(&mut self, width: usize) -> Result<u64> {
+        let bytes: &[u8] = self.take(width)?;
+        let mut buffer: [u8; 8] = [0; 8];
+        buffer[..width].copy_from_slice(bytes);
+        Ok(u64::from_le_bytes(buffer))
+    }
+
+    fn read_signed(&mut self, width: usize) -> Result<i64> {
+        let value: u64 = self.read_le(width)?;
+        let shift: u32 = 64 - (width as u32 * 8);
+        Ok(((value << shift) as i64) >> shift)
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value: u64 = 0;
+        let mut shift: u32 = 0;
+        loop {
+            let byte: u8 = self.take(1)?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+            if shift > 63 {
+                return Err(Error::new("invalid variable length integer"));
+            }
+        }
+    }
+
+    fn read_reverse_varint(&self, end: usize) -> Result<(u64, usize)> {
+        let mut value: u64 = 0;
+        let mut shift: u32 = 0;
+        let mut index: usize = end;
+        loop {
+            index = index.checked_sub(1).ok_or_else(eof)?;
+            let byte: u8 = *self.input.get(index).ok_or_else(eof)?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value, end - index));
+            }
+            shift += 7;
+            if shift > 63 {
+                return Err(Error::new("invalid variable length integer"));
+            }
+        }
+    }
+
+    fn skip_padding(&mut self, end: usize) {
+        while self.position < end && self.input[self.position] == 0x00 {
+            self.position += 1;
+        }
+    }
+
+    fn read_str(&mut self) -> Result<&'de str> {
+        let head: u8 = self.take(1)?[0];
+        let len: usize = match head {
+            0x40..=0xbe => (head - 0x40) as usize,
+            0xbf => self.read_le(8)? as usize,
+            _ => return Err(Error::new("expected a string")),
+        };
+        let bytes: &'de [u8] = self.take(len)?;
+        str::from_utf8(bytes).map_err(|_| Error::new("invalid utf-8 string"))
+    }
+
+    /// Reads the layout of an array or object and returns
+    /// the number of items (if known up front) and the end offset of its items.
+    fn open(&mut self) -> Result<(Option<u64>, usize)> {
+        let start: usize = self.position;
+        let head: u8 = self.take(1)?[0];
+        match head {
+            0x01 | 0x0a => Ok((Some(0), self.position)),
+            0x02..=0x05 => {
+                let end: usize = start + self.read_le(length_width(head))? as usize;
+                self.skip_padding(end);
+                Ok((None, end))
+            },
+            0x06..=0x08 | 0x0b..=0x0d | 0x0f..=0x11 => {
+                let width: usize = length_width(head);
+                let end: usize = start + self.read_le(width)? as usize;
+                let count: u64 = self.read_le(width)?;
+                self.skip_padding(end);
+                Ok((Some(count), end))
+            },
+            0x09 | 0x0e | 0x12 => {
+                let end: usize = start + self.read_le(8)? as usize;
+                let count: u64 = u64::from_le_bytes(
+                    self.input.get(end - 8..end).ok_or_else(eof)?.try_into().unwrap());
+                self.skip_padding(end);
+                Ok((Some(count), end))
+            },
+            0x13 | 0x14 => {
+                let end: usize = start + self.read_varint()? as usize;
+                let (count, _) = self.read_reverse_varint(end)?;
+                Ok((Some(count), end))
+            },
+            _ => Err(Error::new("expected an array or object")),
+        }
+    }
+
+    /// Byte size of the value at the current position.
+    fn value_size(&self) -> Result<usize> {
+        let head: u8 = self.peek()?;
+        let at = |offset: usize, width: usize| -> Result<usize> {
+            let bytes: &[u8] = self.input.get(self.position + offset..self.position + offset + width).ok_or_else(eof)?;
+            let mut buffer: [u8; 8] = [0; 8];
+            buffer[..width].copy_from_slice(bytes);
+            Ok(u64::from_le_bytes(buffer) as usize)
+        };
+        match head {
+            0x01 | 0x0a | 0x18..=0x1a | 0x1e | 0x1f | 0x30..=0x3f => Ok(1),
+            0x1b | 0x1c => Ok(9),
+            0x02..=0x09 | 0x0b..=0x12 => at(1, length_width(head)),
+            0x13 | 0x14 => {
+                let mut cursor: Deserializer = Deserializer { input: self.input, position: self.position + 1 };
+                Ok(cursor.read_varint()? as usize)
+            },
+            0x20..=0x27 => Ok(1 + (head - 0x1f) as usize),
+            0x28..=0x2f => Ok(1 + (head - 0x27) as usize),
+            0x40..=0xbe => Ok(1 + (head - 0x40) as usize),
+            0xbf => Ok(9 + at(1, 8)?),
+            0xc0..=0xc7 => {
+                let width: usize = (head - 0xbf) as usize;
+                Ok(1 + width + at(1, width)?)
+            },
+            _ => Err(Error::new("unsupported value type")),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let head: u8 = self.peek()?;
+        match head {
+            0x18 => {
+                self.position += 1;
+                visitor.visit_unit()
+            },
+            0x19 | 0x1a => {
+                self.position += 1;
+                visitor.visit_bool(head == 0x1a)
+            },
+            0x1b => {
+                self.position += 1;
+                let value: u64 = self.read_le(8)?;
+                visitor.visit_f64(f64::from_bits(value))
+            },
+            0x1c => {
+                self.position += 1;
+                let value: i64 = self.read_signed(8)?;
+                visitor.visit_i64(value)
+            },
+            0x20..=0x27 => {
+                self.position += 1;
+                let value: i64 = self.read_signed((head - 0x1f) as usize)?;
+                visitor.visit_i64(value)
+            },
+            0x28..=0x2f => {
+                self.position += 1;
+                let value: u64 = self.read_le((head - 0x27) as usize)?;
+                visitor.visit_u64(value)
+            },
+            0x30..=0x39 => {
+                self.position += 1;
+                visitor.visit_u64((head - 0x30) as u64)
+            },
+            0x3a..=0x3f => {
+                self.position += 1;
+                visitor.visit_i64(head as i64 - 0x40)
+            },
+            0x40..=0xbf => visitor.visit_borrowed_str(self.read_str()?),
+            0xc0..=0xc7 => {
+                self.position += 1;
+                let len: usize = self.read_le((head - 0xbf) as usize)? as usize;
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            },
+            0x01..=0x09 | 0x13 => {
+                let (count, end) = self.open()?;
+                let value: V::Value = visitor.visit_seq(Items { deserializer: &mut *self, remaining: count, end })?;
+                self.position = end;
+                Ok(value)
+            },
+            0x0a..=0x12 | 0x14 => {
+                let (count, end) = self.open()?;
+                let value: V::Value = visitor.visit_map(Items { deserializer: &mut *self, remaining: count, end })?;
+                self.position = end;
+                Ok(value)
+            },
+            _ => Err(Error::new("unsupported value type")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.peek()? == 0x18 {
+            self.position += 1;
+            return visitor.visit_none();
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        match self.peek()? {
+            0x40..=0xbf => visitor.visit_enum(self.read_str()?.into_deserializer()),
+            0x0b..=0x12 | 0x14 => {
+                let (count, end) = self.open()?;
+                if count != Some(1) {
+                    return Err(Error::new("expected an object with a single variant"));
+                }
+                let value: V::Value = visitor.visit_enum(Enum { deserializer: &mut *self })?;
+                self.position = end;
+                Ok(value)
+            },
+            _ => Err(Error::new("expected an enum")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.position += self.value_size()?;
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// Sequential access to the items of an array or the pairs of an object.
+struct Items<'a, 'de: 'a> {
+    deserializer: &'a mut Deserializer<'de>,
+    remaining: Option<u64>,
+    end: usize
+}
+
+impl<'a, 'de> Items<'a, 'de> {
+    fn has_next(&mut self) -> bool {
+        match self.remaining {
+            Some(0) => false,
+            Some(count) => {
+                self.remaining = Some(count - 1);
+                true
+            },
+            // arrays without an index table run until their byte length
+            None => self.deserializer.position < self.end,
+        }
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for Items<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+}
+
+impl<'de, 'a> MapAccess<'de> for Items<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        let key: &'de str = match self.deserializer.peek()? {
+            head @ 0x31..=0x35 => {
+                self.deserializer.position += 1;
+                translate((head - 0x30) as u64)?
+            },
+            head @ 0x28..=0x2f => {
+                self.deserializer.position += 1;
+                let id: u64 = self.deserializer.read_le((head - 0x27) as usize)?;
+                translate(id)?
+            },
+            _ => self.deserializer.read_str()?,
+        };
+        seed.deserialize(KeyDeserializer { key }).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+}
+
+/// Object keys are strings, integer keys are parsed back from them (like serde_json).
+struct KeyDeserializer<'de> {
+    key: &'de str
+}
+
+macro_rules! deserialize_key_number {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                match self.key.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => visitor.visit_borrowed_str(self.key),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(self.key)
+    }
+
+    deserialize_key_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        visitor.visit_enum(self.key.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct Enum<'a, 'de: 'a> {
+    deserializer: &'a mut Deserializer<'de>
+}
+
+impl<'de, 'a> EnumAccess<'de> for Enum<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let variant: &'de str = self.deserializer.read_str()?;
+        let value: V::Value = seed.deserialize(variant.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, 'a> VariantAccess<'de> for Enum<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(self.deserializer)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self.deserializer)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserializer.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        self.deserializer.deserialize_map(visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde::{Serialize, Deserialize};
+    use serde_json::{json, Value};
+
+    use crate::{vpack, Doc};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Point(i32, i32),
+        Rect { w: u32, h: u32 }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Sample {
+        name: String,
+        small: u8,
+        negative: i64,
+        large: u64,
+        ratio: f64,
+        flag: bool,
+        missing: Option<String>,
+        tags: Vec<String>,
+        shapes: Vec<Shape>,
+        lookup: HashMap<u32, String>
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let sample: Sample = Sample {
+            name: "x".repeat(200),
+            small: 7,
+            negative: -129,
+            large: u64::MAX,
+            ratio: 0.25,
+            flag: true,
+            missing: None,
+            tags: vec![],
+            shapes: vec![Shape::Empty, Shape::Circle(1.5), Shape::Point(-1, 300), Shape::Rect { w: 2, h: 3 }],
+            lookup: HashMap::from([(1, "one".to_owned())])
+        };
+
+        let bytes: Vec<u8> = vpack::to_vec(&sample).unwrap();
+        let decoded: Sample = vpack::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, sample);
+    }
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(vpack::to_vec(&json!([])).unwrap(), vec![0x01]);
+        assert_eq!(vpack::to_vec(&json!({})).unwrap(), vec![0x0a]);
+        assert_eq!(vpack::to_vec(&json!(-3)).unwrap(), vec![0x3d]);
+        assert_eq!(vpack::to_vec(&json!(1000)).unwrap(), vec![0x29, 0xe8, 0x03]);
+        // compact array [1, 2] with byte length 5 and one item count byte
+        assert_eq!(vpack::to_vec(&json!([1, 2])).unwrap(), vec![0x13, 0x05, 0x31, 0x32, 0x02]);
+    }
+
+    #[test]
+    fn test_server_layouts() {
+        // array without index table: [1, 2, 3]
+        let value: Value = vpack::from_slice(&[0x02, 0x05, 0x31, 0x32, 0x33]).unwrap();
+        assert_eq!(value, json!([1, 2, 3]));
+
+        // array with index table: ["a", 1]
+        let value: Value = vpack::from_slice(&[0x06, 0x08, 0x02, 0x41, 0x61, 0x31, 0x03, 0x05]).unwrap();
+        assert_eq!(value, json!(["a", 1]));
+
+        // sorted object with index table and translated keys: { "_key": "k", "a": 1 }
+        let value: Value = vpack::from_slice(&[0x0b, 0x0b, 0x02, 0x31, 0x41, 0x6b, 0x41, 0x61, 0x31, 0x03, 0x06]).unwrap();
+        assert_eq!(value, json!({"_key": "k", "a": 1}));
+    }
+
+    #[test]
+    fn test_flattened_document() {
+        #[derive(Serialize, Deserialize, Debug)]
+        struct Record {
+            body: String
+        }
+
+        let raw: Value = json!({
+            "_id": "record/1",
+            "_key": "1",
+            "_rev": "abc",
+            "body": "some body",
+            "created_on": 1,
+            "modified_on": 2
+        });
+        let doc: Doc<Record> = vpack::from_slice(&vpack::to_vec(&raw).unwrap()).unwrap();
+        assert_eq!(doc.keys.key, "1");
+        assert_eq!(doc.record.body, "some body");
+        assert_eq!(doc.metadata.modified_on, 2);
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "blocking")]
+
+use blandango::{blocking, name, Arango, Config, Doc, NewCollection, Properties, WriteOptions};
+use serde::{Serialize, Deserialize};
+
+mod model;
+
+use crate::model::test_config;
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BlockingData {
+    pub body: String
+}
+
+// Define a document collection
+type BlockingDocument = Doc<BlockingData>;
+
+#[test]
+fn test_blocking_document(){
+    let config: &Config = &test_config();
+    let db: blocking::Database = Arango::new(config);
+    let document: blocking::Document = Arango::new(config);
+
+    let collection: blocking::Collection = db.collection(&name::<BlockingData>());
+    _ = collection.drop();
+
+    let new_collection: NewCollection = NewCollection::default_document_collection(name::<BlockingData>());
+    let _: Properties = db.new_collection(&new_collection).unwrap();
+
+    // create
+    let mut message: BlockingDocument = document.insert(&BlockingData{ body: "blocking data".to_owned() }, &WriteOptions::default()).unwrap().new.unwrap();
+    println!("created message {:#?}", message);
+
+    // update
+    message.record.body = "updated data".to_owned();
+    document.update(&message, &WriteOptions::default()).unwrap();
+
+    // get
+    let msg: BlockingDocument = document.read(&message.keys.key).unwrap();
+    assert_eq!(msg.record.body, "updated data");
+
+    // delete
+    document.destroy(&message).unwrap();
+
+    collection.drop().unwrap();
+}
@@ -79,3 +79,52 @@ async fn test_collection(){
     collection.drop().await.unwrap();
 
 }
+
+
+#[tokio::test]
+async fn test_export(){
+    #[derive(serde::Serialize)]
+    pub struct ExportTest {
+        pub body: String,
+        pub hidden: bool
+    }
+
+    let config: &Config = &test_config();
+    let db: Database = Arango::new(config);
+    let import: Import = Arango::new(config);
+
+    let collection: Collection = db.collection(&name::<ExportTest>());
+    _ = collection.drop().await;
+
+    let new_collection: NewCollection = NewCollection::default_document_collection(name::<ExportTest>());
+    let _: Properties = db.new_collection(&new_collection).await.unwrap();
+
+    let records = futures_util::stream::iter((0..25).map(|i| ExportTest { body: format!("record {}", i), hidden: i % 5 == 0 }));
+    let _: ImportResponse = import.import_stream(records, &ImportQueryParams::default()).await.unwrap();
+
+    let options: ExportOptions = ExportOptions {
+        batch_size: 4,
+        projection: Some(vec!["body".to_owned()]),
+        filter: Some(Filter::eq("hidden", false)),
+        ..Default::default()
+    };
+    let mut output: Vec<u8> = Vec::new();
+    let exported: u64 = collection.export(&mut output, &options).await.unwrap();
+    assert_eq!(exported, 20);
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 20);
+    assert!(lines.iter().all(|line| line.get("hidden").is_none() && line.get("body").is_some()));
+
+    // a single array
+    let options: ExportOptions = ExportOptions { format: ExportFormat::Array, ..Default::default() };
+    let mut output: Vec<u8> = Vec::new();
+    collection.export(&mut output, &options).await.unwrap();
+    let documents: Vec<serde_json::Value> = serde_json::from_slice(&output).unwrap();
+    assert_eq!(documents.len(), 25);
+
+    collection.drop().await.unwrap();
+}
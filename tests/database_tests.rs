@@ -1,4 +1,5 @@
 use blandango::*;
+use serde::Deserialize;
 mod model;
 
 use crate::model::test_config;
@@ -29,4 +30,49 @@ async fn test_database() {
     // drop
     let drop = database.drop(&db.name).await.unwrap();
     println!("drop: {:#?}", drop);
-}
\ No newline at end of file
+}
+
+
+#[tokio::test]
+async fn test_raw_request() {
+    #[derive(Deserialize, Debug)]
+    struct Version {
+        server: String,
+        version: String
+    }
+
+    struct VersionEndpoint;
+
+    impl Endpoint for VersionEndpoint {
+        type Response = Version;
+
+        fn method(&self) -> Method {
+            Method::GET
+        }
+
+        fn path(&self) -> Vec<String> {
+            vec!["_api".to_owned(), "version".to_owned()]
+        }
+    }
+
+    let config: &Config = &test_config();
+    let database: Database = Arango::new(config);
+
+    // raw
+    let response: RawResponse = database.client.request(Method::GET)
+        .path("_api")
+        .path("version")
+        .header("accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status, StatusCode::OK);
+    println!("headers: {:#?}", response.headers);
+
+    let version: Version = response.decode().unwrap();
+    println!("version: {}", version.version);
+
+    // typed
+    let version: Version = database.client.call(&VersionEndpoint).await.unwrap();
+    assert_eq!(version.server, "arango");
+}
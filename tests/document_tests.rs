@@ -46,7 +46,7 @@ async fn test_document(){
 
     let data: &str = r#"
         {
-            "_id": "test_id",
+            "_id": "sample_data/test_key",
             "_key": "test_key",
             "_rev": "test_rev",
             "body": "the inner data"
@@ -59,18 +59,22 @@ async fn test_document(){
     let result: SampleDocument = serde_json::from_str(data).unwrap();
 
     // create 
-    let mut message: SampleDocument = document.insert(&result.record).await.unwrap();
+    let mut message: SampleDocument = document.insert(&result.record, &WriteOptions::default()).await.unwrap().new.unwrap();
     println!("created message {:#?}", message);
 
     // update
     message.record.body = "updated data".to_owned();
 
-    document.update(&message).await.unwrap();
+    // nothing is echoed when silent
+    let response: WriteResult<SampleData> = document.update(&message, &WriteOptions::fast()).await.unwrap();
+    assert!(response.new.is_none());
     println!("updated message {:#?}", message);
 
     // replace
     message.record.body = "replaced data".to_owned();
-    document.replace(&message).await.unwrap();
+    let response: WriteResult<SampleData> = document.replace(&message, &WriteOptions { return_old: true, ..Default::default() }).await.unwrap();
+    assert_eq!(response.old.unwrap().record.body, "updated data");
+    assert_eq!(response.new.unwrap().record.body, "replaced data");
     println!("replaced message {:#?}", message);
 
     // get
@@ -105,15 +109,15 @@ async fn test_edge(){
     let to: ToData = serde_json::from_str(data).unwrap();
 
     // create 
-    let from_doc: FromDocument = document.insert(&from).await.unwrap();
-    let to_doc: ToDocument = document.insert(&to).await.unwrap();
+    let from_doc: FromDocument = document.insert(&from, &WriteOptions::default()).await.unwrap().new.unwrap();
+    let to_doc: ToDocument = document.insert(&to, &WriteOptions::default()).await.unwrap().new.unwrap();
 
     let data: EdgeData = serde_json::from_str(data).unwrap();
     let nat_edge: EdgeDocument = Edge { 
         link: ArangoEdgeKeys { to: to_doc.keys.id, from: from_doc.keys.id }, 
         edge: data 
     };
-    let real_edge: Doc<Edge<EdgeData>> = document.insert(&nat_edge).await.unwrap();
+    let real_edge: Doc<Edge<EdgeData>> = document.insert(&nat_edge, &WriteOptions::default()).await.unwrap().new.unwrap();
     
     /*
     EDGE structure - link keys are nested within the Doc<T> structure.
@@ -148,3 +152,492 @@ async fn test_edge(){
     teardown(&name::<EdgeData>()).await;
 }
 
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VertexData {
+    pub body: String
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LinkData {
+    pub weight: u32
+}
+
+#[tokio::test]
+async fn test_insert_edge(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<VertexData>()).await;
+    let _: Properties = setup_edge_collection(&name::<LinkData>()).await;
+
+    let from: Doc<VertexData> = document.insert(&VertexData { body: "from".to_owned() }, &WriteOptions::default()).await.unwrap().new.unwrap();
+    let to: Doc<VertexData> = document.insert(&VertexData { body: "to".to_owned() }, &WriteOptions::default()).await.unwrap().new.unwrap();
+
+    let options: EdgeOptions = EdgeOptions { verify_vertices: true, ..Default::default() };
+    let edge: Doc<Edge<LinkData>> = document.insert_edge(&from, &to, LinkData { weight: 1 }, &options).await.unwrap();
+    assert_eq!(edge.record.link.from, from.keys.id);
+    assert_eq!(edge.record.link.to, to.keys.id);
+    assert_eq!(edge.record.edge.weight, 1);
+
+    let outbound: EdgesResponse<LinkData> = document.edges(&from.keys.id, Direction::Out).await.unwrap();
+    assert_eq!(outbound.edges.len(), 1);
+    assert_eq!(outbound.edges[0].keys.key, edge.keys.key);
+    assert!(document.edges::<LinkData>(&from.keys.id, Direction::In).await.unwrap().edges.is_empty());
+    assert_eq!(document.edges::<LinkData>(&to.keys.id, Direction::Any).await.unwrap().edges.len(), 1);
+
+    // vertices may be given by id, whatever the metadata policy of their handle
+    let plain: Document<NoMetadata> = Arango::new(config);
+    let vertex: Doc<VertexData, NoMetadata> = plain.insert(&VertexData { body: "plain".to_owned() }, &WriteOptions::default()).await.unwrap().new.unwrap();
+    let edge: Doc<Edge<LinkData>> = document.insert_edge(&vertex, &to.keys.id, LinkData { weight: 3 }, &options).await.unwrap();
+    assert_eq!(edge.record.link.from, vertex.keys.id);
+
+    // an id of the vertex collection is refused for edges
+    let result = document.read::<LinkData>(&from.keys.id).await;
+    assert!(matches!(result, Err(ClientError::Validation(_))));
+    let _: Doc<VertexData> = document.read(&from.keys.id).await.unwrap();
+
+    // the vertex is gone
+    let _: WriteResult<VertexData> = document.destroy(&to).await.unwrap();
+    let result = document.insert_edge(&from, &to, LinkData { weight: 2 }, &options).await;
+    assert!(matches!(result, Err(ClientError::Validation(_))));
+
+    // VertexData is not an edge collection
+    let result = document.insert_edge(&from, &from, VertexData { body: "edge".to_owned() }, &EdgeOptions::default()).await;
+    assert!(matches!(result, Err(ClientError::Validation(_))));
+
+    teardown(&name::<VertexData>()).await;
+    teardown(&name::<LinkData>()).await;
+}
+
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpsertData {
+    pub body: String,
+    pub note: Option<String>
+}
+
+#[tokio::test]
+async fn test_upsert(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<UpsertData>()).await;
+
+    // created
+    let created: WriteResult<UpsertData> = document.upsert("one", &UpsertData { body: "first".to_owned(), note: Some("kept".to_owned()) }, &InsertOptions::default()).await.unwrap();
+    assert!(created.created());
+    let created_on: u64 = created.new.as_ref().unwrap().metadata.created_on;
+
+    // merged into the existing document
+    // the overwrite mode is forced to Update, null attributes are removed
+    let options: InsertOptions = InsertOptions {
+        overwrite_mode: Some(OverwriteMode::Replace),
+        write: WriteOptions { keep_null: Some(false), return_old: true, ..Default::default() }
+    };
+    let updated: WriteResult<UpsertData> = document.upsert("one", &UpsertData { body: "second".to_owned(), note: None }, &options).await.unwrap();
+    assert!(!updated.created());
+    assert_eq!(updated.meta.as_ref().unwrap().old_rev.as_ref(), created.rev());
+    let new: Doc<UpsertData> = updated.new.unwrap();
+    assert_eq!(new.record.body, "second");
+    assert_eq!(new.metadata.created_on, created_on);
+    assert_eq!(updated.old.unwrap().record.note.as_deref(), Some("kept"));
+    let dynamic: DynDocument = Arango::new(config);
+    let stored: serde_json::Value = dynamic.read(&name::<UpsertData>(), "one").await.unwrap();
+    assert_eq!(stored.get("note"), None);
+
+    teardown(&name::<UpsertData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PatchData {
+    pub body: String,
+    pub flag: bool,
+    pub note: Option<String>
+}
+
+blandango::patch! {
+    pub struct PatchDataPatch for PatchData {
+        pub flag: bool,
+        pub note: Option<String>
+    }
+}
+
+#[tokio::test]
+async fn test_patch(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<PatchData>()).await;
+
+    let doc: Doc<PatchData> = document.insert(&PatchData { body: "body".to_owned(), flag: false, note: Some("note".to_owned()) }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+
+    // a concurrent writer changes the body
+    let _: WriteResult<PatchData> = document.patch(&doc.keys.key, &serde_json::json!({ "body": "concurrent" }), &WriteOptions::default()).await.unwrap();
+
+    // only the flag is sent, the body is kept and the note removed
+    let patch: PatchDataPatch = PatchDataPatch { flag: true.into(), note: Field::Remove };
+    let options: WriteOptions = WriteOptions { keep_null: Some(false), ..Default::default() };
+    let patched: Doc<PatchData> = document.patch(&doc.keys.key, &patch, &options).await.unwrap().new.unwrap();
+
+    assert_eq!(patched.record.body, "concurrent");
+    assert!(patched.record.flag);
+    assert!(patched.record.note.is_none());
+    assert_eq!(patched.metadata.created_on, doc.metadata.created_on);
+
+    teardown(&name::<PatchData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TrackedData {
+    pub body: String,
+    pub flag: bool,
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>
+}
+
+#[tokio::test]
+async fn test_tracked(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<TrackedData>()).await;
+
+    let record: TrackedData = TrackedData { body: "body".to_owned(), flag: false, note: Some("note".to_owned()), tag: Some("tag".to_owned()) };
+    let doc: Doc<TrackedData> = document.insert(&record, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+
+    let mut tracked: Tracked<TrackedData> = document.read_tracked(&doc.keys.key).await.unwrap();
+    let mut stale: Tracked<TrackedData> = document.read_tracked(&doc.keys.key).await.unwrap();
+
+    // nothing changed, nothing is sent
+    assert!(document.save(&mut tracked).await.unwrap().is_none());
+
+    tracked.record.flag = true;
+    let result: WriteResult<TrackedData> = document.save(&mut tracked).await.unwrap().unwrap();
+    assert_eq!(result.rev(), Some(&tracked.keys.rev));
+    assert!(tracked.changes().unwrap().is_none());
+
+    // a null is stored, a removed attribute is unset
+    tracked.record.note = None;
+    tracked.record.tag = None;
+    let _: WriteResult<TrackedData> = document.save(&mut tracked).await.unwrap().unwrap();
+    let dynamic: DynDocument = Arango::new(config);
+    let stored: serde_json::Value = dynamic.read(&name::<TrackedData>(), &doc.keys.key).await.unwrap();
+    assert_eq!(stored.get("note"), Some(&serde_json::Value::Null));
+    assert_eq!(stored.get("tag"), None);
+    assert!(tracked.removals().unwrap().is_none());
+
+    // the stale copy fails the revision precondition
+    stale.record.body = "stale".to_owned();
+    assert!(document.save(&mut stale).await.is_err());
+
+    teardown(&name::<TrackedData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConditionalData {
+    pub body: String
+}
+
+#[tokio::test]
+async fn test_conditional_read(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<ConditionalData>()).await;
+
+    let doc: Doc<ConditionalData> = document.insert(&ConditionalData { body: "body".to_owned() }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+
+    let rev: DocumentRevision = document.read_header::<ConditionalData>(&doc.keys.key).await.unwrap();
+    assert_eq!(rev, doc.keys.rev);
+    assert!(document.read_header::<ConditionalData>("missing").await.is_err());
+
+    // the local copy is still current
+    let read: Conditional<ConditionalData> = document.read_if_changed(&doc.keys.key, &rev).await.unwrap();
+    assert!(matches!(read, Conditional::NotModified));
+
+    let _: WriteResult<ConditionalData> = document.patch(&doc.keys.key, &serde_json::json!({ "body": "changed" }), &WriteOptions::default()).await.unwrap();
+
+    match document.read_if_changed::<ConditionalData>(&doc.keys.key, &rev).await.unwrap() {
+        Conditional::Modified(changed) => assert_eq!(changed.record.body, "changed"),
+        Conditional::NotModified => panic!("document changed"),
+    }
+
+    teardown(&name::<ConditionalData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CachedData {
+    pub body: String
+}
+
+#[tokio::test]
+async fn test_cached_read(){
+    let config: &Config = &test_config();
+    let document: Document = Document::from(Client::new(config))
+        .with_cache(CacheOptions { capacity: 10, ttl: std::time::Duration::ZERO });
+
+    let _: Properties = setup_collection(&name::<CachedData>()).await;
+
+    let first: Doc<CachedData> = document.insert(&CachedData { body: "first".to_owned() }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+    let second: Doc<CachedData> = document.insert(&CachedData { body: "second".to_owned() }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+
+    // cached, then revalidated by revision
+    let read: Doc<CachedData> = document.read(&first.keys.key).await.unwrap();
+    assert_eq!(read.record.body, "first");
+    let read: Doc<CachedData> = document.read(&first.keys.key).await.unwrap();
+    assert_eq!(read.keys.rev, first.keys.rev);
+
+    // writes through the handle invalidate the entry
+    let _: WriteResult<CachedData> = document.patch(&first.keys.key, &serde_json::json!({ "body": "changed" }), &WriteOptions::default()).await.unwrap();
+    let read: Doc<CachedData> = document.read(&first.keys.key).await.unwrap();
+    assert_eq!(read.record.body, "changed");
+
+    // a write by another handle is picked up on revalidation
+    let other: Document = Arango::new(config);
+    let _: WriteResult<CachedData> = other.patch(&first.keys.key, &serde_json::json!({ "body": "other" }), &WriteOptions::default()).await.unwrap();
+    let read: Doc<CachedData> = document.read(&first.keys.key).await.unwrap();
+    assert_eq!(read.record.body, "other");
+
+    // key order is kept with partial hits
+    let many: Vec<Doc<CachedData>> = document.read_many(&[second.keys.key.clone(), first.keys.key.clone()]).await.unwrap();
+    assert_eq!(many[0].record.body, "second");
+    assert_eq!(many[1].record.body, "other");
+
+    // a document deleted by another handle is dropped from the cache
+    let _: WriteResult<CachedData> = other.delete(&second.keys.key, &WriteOptions::default()).await.unwrap();
+    assert!(matches!(document.read::<CachedData>(&second.keys.key).await, Err(ClientError::Api(error)) if error.code == 404));
+    assert!(matches!(document.read::<CachedData>(&second.keys.key).await, Err(ClientError::Api(error)) if error.code == 404));
+
+    teardown(&name::<CachedData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AuthorData {
+    pub name: String
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BookData {
+    pub title: String,
+    pub author: DocumentId
+}
+
+#[tokio::test]
+async fn test_read_ids(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<AuthorData>()).await;
+    let _: Properties = setup_collection(&name::<BookData>()).await;
+
+    let author: Doc<AuthorData> = document.insert(&AuthorData { name: "author".to_owned() }, &WriteOptions::default()).await.unwrap().new.unwrap();
+    let book: Doc<BookData> = document.insert(&BookData { title: "book".to_owned(), author: author.keys.id.clone() }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+
+    // follow the stored reference
+    let read: Doc<AuthorData> = document.read_by_id(&book.record.author).await.unwrap();
+    assert_eq!(read.record.name, "author");
+
+    let missing: DocumentId = DocumentId::of::<AuthorData>(DocumentKey::new("missing").unwrap());
+    let ids: Vec<DocumentId> = vec![author.keys.id.clone(), book.keys.id.clone(), missing.clone()];
+    let documents = document.read_ids(&ids).await.unwrap();
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[&author.keys.id]["name"], "author");
+    assert_eq!(documents[&book.keys.id]["title"], "book");
+    assert!(!documents.contains_key(&missing));
+
+    teardown(&name::<AuthorData>()).await;
+    teardown(&name::<BookData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidatedData {
+    pub body: String
+}
+
+impl Validator for ValidatedData {
+    fn validate(&self) -> Result<()> {
+        if self.body.is_empty() {
+            return Err(ClientError::validation_error("body is empty".to_owned()));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_validated_writes(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<ValidatedData>()).await;
+
+    let invalid: ValidatedData = ValidatedData { body: "".to_owned() };
+    assert!(matches!(document.insert_validated(&invalid, &WriteOptions::default()).await, Err(ClientError::Validation(_))));
+
+    let mut doc: Doc<ValidatedData> = document.insert_validated(&ValidatedData { body: "valid".to_owned() }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+
+    doc.record.body = "".to_owned();
+    assert!(matches!(document.update_validated(&doc, &WriteOptions::default()).await, Err(ClientError::Validation(_))));
+    assert!(matches!(document.replace_validated(&doc, &WriteOptions::default()).await, Err(ClientError::Validation(_))));
+
+    // the failures of every record are reported, nothing is inserted
+    let models: Vec<ValidatedData> = vec![
+        ValidatedData { body: "".to_owned() },
+        ValidatedData { body: "valid".to_owned() },
+        ValidatedData { body: "".to_owned() }
+    ];
+    match document.insert_many_validated(&models).await {
+        Err(ClientError::ValidationFailures(failures)) => assert_eq!(failures.len(), 2),
+        other => panic!("expected validation failures, got {:?}", other),
+    }
+
+    let collection: Collection = Collection::new(Client::new(config), name::<ValidatedData>());
+    assert_eq!(collection.count().await.unwrap().count, 1);
+
+    teardown(&name::<ValidatedData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlainData {
+    pub body: String
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SecondsData {
+    pub body: String
+}
+
+#[tokio::test]
+async fn test_metadata_policies(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+    let db: Database = Arango::new(config);
+
+    // no computed values, documents only carry their keys
+    teardown(&name::<PlainData>()).await;
+    let _: Properties = db.new_collection(&NewCollection::new(name::<PlainData>(), false, None)).await.unwrap();
+
+    let plain_document: Document<NoMetadata> = Arango::new(config);
+    let plain: Doc<PlainData, NoMetadata> = plain_document.insert(&PlainData { body: "plain".to_owned() }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+    let read: Doc<PlainData, NoMetadata> = plain_document.read(&plain.keys.key).await.unwrap();
+    assert_eq!(read.record.body, "plain");
+    assert!(document.read::<PlainData>(&plain.keys.key).await.is_err());
+
+    // timestamps in seconds
+    teardown(&name::<SecondsData>()).await;
+    let computed_values: Vec<ComputedValue> = Timestamps::<Seconds>::computed_values();
+    let _: Properties = db.new_collection(&NewCollection::new(name::<SecondsData>(), false, Some(computed_values))).await.unwrap();
+
+    let seconds_document: Document<Timestamps<Seconds>> = Arango::new(config);
+    let before: u64 = get_current_timestamp(TimeUnit::Seconds);
+    let doc: Doc<SecondsData, Timestamps<Seconds>> = seconds_document.insert(&SecondsData { body: "seconds".to_owned() }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+    assert!(doc.metadata.created_on >= before && doc.metadata.created_on <= get_current_timestamp(TimeUnit::Seconds));
+
+    teardown(&name::<PlainData>()).await;
+    teardown(&name::<SecondsData>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HookedData {
+    pub body: String,
+    pub revision: u32
+}
+
+/// Counts the revisions of a record and keeps a log of the writes
+#[derive(Default)]
+pub struct Audit {
+    pub log: std::sync::Arc<std::sync::Mutex<Vec<String>>>
+}
+
+impl Hooks<HookedData> for Audit {
+    fn before_insert(&self, record: &mut HookedData) -> Result<()> {
+        record.revision = 1;
+        Ok(())
+    }
+
+    fn after_insert(&self, written: &Written<HookedData>) -> Result<()> {
+        // the record as changed by before_insert
+        let revision: u32 = written.record.unwrap().revision;
+        self.log.lock().unwrap().push(format!("insert {} {}", written.meta.unwrap().key, revision));
+        Ok(())
+    }
+
+    fn before_update(&self, _key: &str, record: &mut HookedData) -> Result<()> {
+        record.revision += 1;
+        Ok(())
+    }
+
+    fn before_patch(&self, key: &str, patch: &serde_json::Value) -> Result<()> {
+        if patch.get("revision").is_some() {
+            return Err(ClientError::validation_error("revision is counted by the hooks".to_owned()));
+        }
+        self.log.lock().unwrap().push(format!("patch {}", key));
+        Ok(())
+    }
+
+    fn after_update(&self, key: &str, written: &Written<HookedData>) -> Result<()> {
+        let revision: u32 = written.new.unwrap().revision;
+        self.log.lock().unwrap().push(format!("update {} {}", key, revision));
+        Ok(())
+    }
+
+    fn before_delete(&self, key: &str) -> Result<()> {
+        if key == "protected" {
+            return Err(ClientError::validation_error("protected document".to_owned()));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_hooks(){
+    let config: &Config = &test_config();
+    let audit: Audit = Audit::default();
+    let log = audit.log.clone();
+    let document: Document = Document::from(Client::new(config)).with_hooks(audit);
+
+    let _: Properties = setup_collection(&name::<HookedData>()).await;
+
+    let mut doc: Doc<HookedData> = document.insert(&HookedData { body: "body".to_owned(), revision: 0 }, &WriteOptions::default())
+        .await.unwrap().new.unwrap();
+    assert_eq!(doc.record.revision, 1);
+
+    doc.record.body = "updated".to_owned();
+    let updated: Doc<HookedData> = document.update(&doc, &WriteOptions::default()).await.unwrap().new.unwrap();
+    assert_eq!(updated.record.revision, 2);
+
+    assert_eq!(*log.lock().unwrap(), vec![format!("insert {} 1", doc.keys.key), format!("update {} 2", doc.keys.key)]);
+
+    // patches are checked before they are sent
+    let result: Result<WriteResult<HookedData>> = document.patch(&doc.keys.key, &serde_json::json!({ "revision": 9 }), &WriteOptions::default()).await;
+    assert!(matches!(result, Err(ClientError::Validation(_))));
+    let _: WriteResult<HookedData> = document.patch(&doc.keys.key, &serde_json::json!({ "body": "patched" }), &WriteOptions::default()).await.unwrap();
+    assert_eq!(log.lock().unwrap()[2..], [format!("patch {}", doc.keys.key), format!("update {} 2", doc.keys.key)]);
+
+    // aborted before the request
+    let _: WriteResult<HookedData> = document.upsert("protected", &HookedData { body: "body".to_owned(), revision: 0 }, &InsertOptions::default()).await.unwrap();
+    let result: Result<WriteResult<HookedData>> = document.delete("protected", &WriteOptions::default()).await;
+    assert!(matches!(result, Err(ClientError::Validation(_))));
+    let _: Doc<HookedData> = document.read("protected").await.unwrap();
+
+    teardown(&name::<HookedData>()).await;
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use blandango::*;
+use serde::{Serialize, Deserialize};
+mod model;
+
+use crate::model::{test_config, setup_collection, teardown};
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DumpData {
+    pub body: String
+}
+
+type DumpDocument = Doc<DumpData>;
+
+#[tokio::test]
+async fn test_dump_restore(){
+    let config: &Config = &test_config();
+    let document: Document = Arango::new(config);
+    let index: Index = Arango::new(config);
+    let dump: Dump = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<DumpData>()).await;
+
+    let first: DumpDocument = document.insert(&DumpData { body: "first".to_owned() }, &WriteOptions::default()).await.unwrap().new.unwrap();
+    let _: DumpDocument = document.insert(&DumpData { body: "second".to_owned() }, &WriteOptions::default()).await.unwrap().new.unwrap();
+
+    let new_index: NewIdx = NewIdx {
+        collection: name::<DumpData>(),
+        name: "body_index".to_owned(),
+        fields: vec!["body".to_owned()],
+        ..Default::default()
+    };
+    let _: IndexResponse = index.create(&new_index).await.unwrap();
+
+    let directory: PathBuf = std::env::temp_dir().join("blandango_dump_test");
+    _ = std::fs::remove_dir_all(&directory);
+
+    let options: DumpOptions = DumpOptions { collections: Some(vec![name::<DumpData>()]), ..Default::default() };
+    let dumped: Vec<DumpedCollection> = dump.dump(&directory, &options).await.unwrap();
+    println!("dumped {:#?}", dumped);
+    assert_eq!(dumped[0].documents, 2);
+    assert!(directory.join("dump_data.structure.json").exists());
+    assert!(directory.join("dump_data.data.json").exists());
+
+    // restore over the existing collection
+    let options: RestoreOptions = RestoreOptions { collections: None, overwrite: true };
+    let restored: Vec<RestoredCollection> = dump.restore(&directory, &options).await.unwrap();
+    println!("restored {:#?}", restored);
+    assert_eq!(restored[0].import.as_ref().unwrap().created, 2);
+
+    // keys, revisions and computed values survive the round trip
+    let doc: DumpDocument = document.read(&first.keys.key).await.unwrap();
+    assert_eq!(doc.record.body, "first");
+    assert_eq!(doc.metadata.created_on, first.metadata.created_on);
+
+    let indexes: Vec<Idx> = index.read(&name::<DumpData>()).await.unwrap();
+    assert!(indexes.iter().any(|idx| idx.name == "body_index"));
+
+    teardown(&name::<DumpData>()).await;
+    _ = std::fs::remove_dir_all(&directory);
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DumpFixture {
+    pub name: String,
+    pub email: String
+}
+
+#[tokio::test]
+async fn test_restore_arangodump(){
+    let config: &Config = &test_config();
+    let document: Document<NoMetadata> = Arango::new(config);
+    let index: Index = Arango::new(config);
+    let dump: Dump = Arango::new(config);
+
+    // written by arangodump, with a hashed data file name
+    let directory: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/arangodump");
+    let options: RestoreOptions = RestoreOptions { collections: Some(vec![name::<DumpFixture>()]), overwrite: true };
+    let restored: Vec<RestoredCollection> = dump.restore(&directory, &options).await.unwrap();
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[0].import.as_ref().unwrap().created, 3);
+
+    let doc: Doc<DumpFixture, NoMetadata> = document.read("21441").await.unwrap();
+    assert_eq!(doc.record.name, "Ada");
+
+    let indexes: Vec<Idx> = index.read(&name::<DumpFixture>()).await.unwrap();
+    assert!(indexes.iter().any(|idx| idx.name == "idx_1780423598412365824" && idx.unique == Some(true)));
+
+    teardown(&name::<DumpFixture>()).await;
+}
@@ -0,0 +1,44 @@
+use blandango::*;
+use serde_json::{json, Value};
+mod model;
+
+use crate::model::{test_config, setup_collection, teardown};
+
+
+#[tokio::test]
+async fn test_dyn_document(){
+    let config: &Config = &test_config();
+    let documents: DynDocument = Arango::new(config);
+    let collection: &str = "dyn_data";
+
+    let _: Properties = setup_collection(collection).await;
+
+    let created: DynWriteResult = documents.insert(collection, &json!({ "_key": "one", "body": "first", "nested": { "a": 1 } }), &WriteOptions::default())
+        .await.unwrap();
+    assert_eq!(created.new.as_ref().unwrap()["body"], "first");
+    let meta: WriteMeta = created.meta.unwrap();
+    assert_eq!(meta.id, "dyn_data/one");
+
+    let read: Value = documents.read(collection, "one").await.unwrap();
+    assert_eq!(read["nested"]["a"], 1);
+
+    // ids of other collections are refused
+    assert!(documents.read(collection, &DocumentId::parse("other/one").unwrap()).await.is_err());
+    let _: Value = documents.read(collection, &meta.id).await.unwrap();
+
+    let updated: DynWriteResult = documents.update(collection, "one", &json!({ "nested": { "b": 2 } }), &WriteOptions::default()).await.unwrap();
+    assert_eq!(updated.new.unwrap()["nested"], json!({ "a": 1, "b": 2 }));
+
+    let replaced: DynWriteResult = documents.replace(collection, "one", &json!({ "body": "replaced" }), &WriteOptions::default()).await.unwrap();
+    assert!(replaced.new.unwrap().get("nested").is_none());
+
+    let many: Vec<Value> = documents.read_many(collection, &["one", "missing"]).await.unwrap();
+    assert_eq!(many[0]["body"], "replaced");
+    assert_eq!(many[1]["error"], true);
+
+    let deleted: DynWriteResult = documents.delete(collection, "one", &WriteOptions { return_old: true, ..Default::default() }).await.unwrap();
+    assert_eq!(deleted.old.unwrap()["body"], "replaced");
+    assert!(documents.read(collection, "one").await.is_err());
+
+    teardown(collection).await;
+}
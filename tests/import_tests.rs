@@ -0,0 +1,44 @@
+use blandango::*;
+use futures_util::stream;
+use serde::{Serialize, Deserialize};
+mod model;
+
+use crate::model::{test_config, setup_collection, teardown};
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportData {
+    pub body: String
+}
+
+#[tokio::test]
+async fn test_import(){
+    let config: &Config = &test_config();
+    let import: Import = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<ImportData>()).await;
+
+    // stream of records
+    let records = stream::iter((0..100).map(|i| ImportData { body: format!("record {}", i) }));
+    let response: ImportResponse = import.import_stream(records, &ImportQueryParams::default()).await.unwrap();
+    println!("imported records {:#?}", response);
+    assert_eq!(response.created, 100);
+
+    // JSONL reader, duplicate keys are updated
+    let lines: &str = "{\"_key\": \"one\", \"body\": \"first\"}\n{\"_key\": \"one\", \"body\": \"second\"}\n\n";
+    let mut params: ImportQueryParams = ImportQueryParams { on_duplicate: Some(OnDuplicate::Update), ..Default::default() };
+
+    let response: ImportResponse = import.import_reader(&name::<ImportData>(), lines.as_bytes(), &params).await.unwrap();
+    println!("imported lines {:#?}", response);
+    assert_eq!(response.created, 1);
+    assert_eq!(response.updated, 1);
+    assert_eq!(response.empty, 1);
+
+    // duplicate keys are reported
+    params.on_duplicate = Some(OnDuplicate::Error);
+    let response: ImportResponse = import.import_reader(&name::<ImportData>(), lines.as_bytes(), &params).await.unwrap();
+    assert_eq!(response.errors, 2);
+    assert_eq!(response.details.len(), 2);
+
+    teardown(&name::<ImportData>()).await;
+}
@@ -88,6 +88,21 @@ async fn test_query(){
     println!("Results: {:#?}", results);
     assert_eq!(results.result.len(), 1);
 
+    // Streamed Cursor (one document per batch)
+    let request: CursorRequest = CursorRequest {
+        query: "FOR R IN sample_data RETURN R".to_owned(),
+        batch_size: Some(1),
+        ..Default::default()
+    };
+
+    let mut stream: CursorStream<SampleDocument> = query.cursor_stream(&request).await.unwrap();
+    let mut streamed: usize = 0;
+    while let Some(doc) = stream.next().await.unwrap() {
+        println!("Streamed: {:#?}", doc);
+        streamed += 1;
+    }
+    assert_eq!(streamed, data.len());
+
     // Cache Entries
     let entries: Vec<Entry> = query.cache_entries().await.unwrap();
     println!("Entries: {:#?}", entries);
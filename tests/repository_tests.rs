@@ -0,0 +1,171 @@
+use blandango::*;
+use serde::{Serialize, Deserialize};
+mod model;
+
+use crate::model::{test_config, setup_collection, setup_edge_collection, teardown};
+
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Member {
+    pub name: String,
+    pub role: String,
+    pub age: u32,
+    pub address: Address
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Address {
+    pub city: String
+}
+
+fn member(name: &str, role: &str, age: u32, city: &str) -> Member {
+    Member { name: name.to_owned(), role: role.to_owned(), age, address: Address { city: city.to_owned() } }
+}
+
+#[tokio::test]
+async fn test_repository(){
+    let config: &Config = &test_config();
+    let members: Repository<Member> = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<Member>()).await;
+
+    let ada: Doc<Member> = members.insert(&member("ada", "admin", 36, "London")).await.unwrap();
+    let _: Doc<Member> = members.insert(&member("bob", "user", 17, "Berlin")).await.unwrap();
+    let _: Doc<Member> = members.insert(&member("eve", "user", 42, "Berlin")).await.unwrap();
+
+    assert_eq!(members.get(&ada.keys.key).await.unwrap().record.name, "ada");
+    assert!(members.exists(&ada.keys.id).await.unwrap());
+    assert!(!members.exists("missing").await.unwrap());
+    assert_eq!(members.get_many(&[&ada.keys.key]).await.unwrap().len(), 1);
+
+    // queries
+    assert_eq!(members.count().await.unwrap(), 3);
+    assert_eq!(members.find_by("role", "user").await.unwrap().len(), 2);
+    let adults: Vec<Doc<Member>> = members.find(&Filter::eq("address.city", "Berlin").and(Filter::gte("age", 18))).await.unwrap();
+    assert_eq!(adults.len(), 1);
+    assert_eq!(adults[0].record.name, "eve");
+    assert_eq!(members.count_where(&Filter::like("name", "%e%")).await.unwrap(), 1);
+
+    // values are bound, not interpolated
+    assert!(members.find_by("name", "\" || true || \"").await.unwrap().is_empty());
+
+    let page: Page<Member> = members.page(1, 1, &[Sort::asc("name")]).await.unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].record.name, "bob");
+
+    let mut stream: CursorStream<Doc<Member>> = members.stream_all().await.unwrap();
+    let mut streamed: usize = 0;
+    while stream.next().await.unwrap().is_some() {
+        streamed += 1;
+    }
+    assert_eq!(streamed, 3);
+
+    // writes
+    let mut doc: Doc<Member> = ada;
+    doc.record.role = "owner".to_owned();
+    let saved: Doc<Member> = members.save(&doc).await.unwrap();
+    assert_eq!(saved.record.role, "owner");
+    assert_eq!(saved.metadata.created_on, doc.metadata.created_on);
+
+    members.delete(&doc.keys.key).await.unwrap();
+    assert_eq!(members.count().await.unwrap(), 2);
+
+    teardown(&name::<Member>()).await;
+}
+
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Post {
+    pub title: String
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Author {
+    pub name: String
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Label {
+    pub text: String
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Labelled {}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Shelf {
+    pub name: String
+}
+
+// posts hold an author_id
+impl Provider<Post> for Author {}
+
+impl Provider<Post> for Label {
+    fn relation() -> Relation {
+        Relation::edges::<Labelled>()
+    }
+}
+
+// author is not an edge collection
+impl Provider<Post> for Shelf {
+    fn relation() -> Relation {
+        Relation::edges::<Author>()
+    }
+}
+
+#[tokio::test]
+async fn test_relations(){
+    let config: &Config = &test_config();
+    let authors: Repository<Author> = Arango::new(config);
+    let labels: Repository<Label> = Arango::new(config);
+    let posts: Repository<Post> = Arango::new(config);
+
+    let _: Properties = setup_collection(&name::<Author>()).await;
+    let _: Properties = setup_collection(&name::<Label>()).await;
+    let _: Properties = setup_collection(&name::<Post>()).await;
+    let _: Properties = setup_edge_collection(&name::<Labelled>()).await;
+    let _: Properties = setup_collection(&name::<Shelf>()).await;
+
+    let ada: Doc<Author> = authors.insert(&Author { name: "ada".to_owned() }).await.unwrap();
+    let bob: Doc<Author> = authors.insert(&Author { name: "bob".to_owned() }).await.unwrap();
+
+    // one-to-many
+    let first: WithParent<Post, Author> = posts.add_child(&ada.keys.key, &Post { title: "first".to_owned() }).await.unwrap();
+    assert_eq!(first.parent.unwrap().record.name, "ada");
+    let _: WithParent<Post, Author> = posts.add_child(&ada.keys.key, &Post { title: "second".to_owned() }).await.unwrap();
+    let _: WithParent<Post, Author> = posts.add_child(&bob.keys.key, &Post { title: "third".to_owned() }).await.unwrap();
+    assert!(posts.add_child::<Author>("missing", &Post { title: "orphan".to_owned() }).await.is_err());
+
+    assert_eq!(posts.children::<Author>(&ada.keys.key).await.unwrap().len(), 2);
+    let parent: Doc<Author> = posts.parent::<Author>(&first.doc.keys.key).await.unwrap().unwrap();
+    assert_eq!(parent.keys.id, ada.keys.id);
+
+    let loaded: Vec<WithParent<Post, Author>> = posts.with_parent(&Filter::eq("title", "third")).await.unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].parent.as_ref().unwrap().record.name, "bob");
+
+    // many-to-many
+    let rust: Doc<Label> = labels.insert(&Label { text: "rust".to_owned() }).await.unwrap();
+    let tagged: WithParent<Post, Label> = posts.add_child(&rust.keys.key, &Post { title: "fourth".to_owned() }).await.unwrap();
+    assert_eq!(posts.children::<Label>(&rust.keys.key).await.unwrap().len(), 1);
+    assert_eq!(posts.parents::<Label>(&tagged.doc.keys.key).await.unwrap().len(), 1);
+    assert!(posts.parent::<Label>(&first.doc.keys.key).await.unwrap().is_none());
+
+    // no child is left behind without its edge
+    let shelves: Repository<Shelf> = Arango::new(config);
+    let shelf: Doc<Shelf> = shelves.insert(&Shelf { name: "top".to_owned() }).await.unwrap();
+    let result: Result<WithParent<Post, Shelf>> = posts.add_child(&shelf.keys.key, &Post { title: "lost".to_owned() }).await;
+    assert!(matches!(result, Err(ClientError::Validation(_))));
+    assert_eq!(posts.count().await.unwrap(), 4);
+
+    let loaded: Vec<WithParent<Post, Label>> = posts.with_parent(&Filter::like("title", "%o%")).await.unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert!(loaded.iter().any(|post| post.doc.record.title == "second" && post.parent.is_none()));
+
+    teardown(&name::<Shelf>()).await;
+    teardown(&name::<Labelled>()).await;
+    teardown(&name::<Post>()).await;
+    teardown(&name::<Label>()).await;
+    teardown(&name::<Author>()).await;
+}